@@ -0,0 +1,134 @@
+use std::{collections::BTreeMap, fs};
+
+use toml::{Table, Value};
+
+use crate::error::Error;
+use crate::solver::{Answer, Part};
+
+/// Expected answers loaded from a manifest such as:
+///
+/// ```toml
+/// [day5]
+/// part1 = "CMZ"
+/// part2 = "MCD"
+/// ```
+#[derive(PartialEq, Debug, Default)]
+pub struct Answers {
+    storage: BTreeMap<(u32, Part), Answer>,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Answers, Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Answers, Error> {
+        let table: Table = content
+            .parse()
+            .map_err(|e: toml::de::Error| Error::toml(content, &e))?;
+
+        let mut result = Answers::default();
+        for (day_key, parts_value) in table.iter() {
+            let day = Self::parse_day(day_key)?;
+            let parts = parts_value
+                .as_table()
+                .ok_or_else(|| Error::parse(format!("expected table for key '{}'", day_key)))?;
+
+            for (part_key, answer_value) in parts.iter() {
+                let part = Self::parse_part(day_key, part_key)?;
+                let answer = match answer_value {
+                    Value::Integer(value) => Answer::Number(*value),
+                    Value::String(value) => Answer::Text(value.clone()),
+                    _ => {
+                        return Err(Error::parse(format!(
+                            "expected integer or string for key '{}.{}'",
+                            day_key, part_key
+                        )))
+                    }
+                };
+                result.storage.insert((day, part), answer);
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&Answer> {
+        self.storage.get(&(day, part))
+    }
+
+    fn parse_day(key: &str) -> Result<u32, Error> {
+        key.strip_prefix("day")
+            .and_then(|x| x.parse().ok())
+            .ok_or_else(|| Error::parse(format!("invalid day key '{}'", key)))
+    }
+
+    fn parse_part(day_key: &str, key: &str) -> Result<Part, Error> {
+        match key {
+            "part1" => Ok(Part::One),
+            "part2" => Ok(Part::Two),
+            _ => Err(Error::parse(format!(
+                "invalid part key '{}.{}'",
+                day_key, key
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers() {
+        let answers =
+            Answers::parse("[day1]\npart1 = 24000\npart2 = 45000\n\n[day5]\npart1 = \"CMZ\"\n")
+                .unwrap();
+
+        assert_eq!(Some(&Answer::Number(24000)), answers.get(1, Part::One));
+        assert_eq!(Some(&Answer::Number(45000)), answers.get(1, Part::Two));
+        assert_eq!(
+            Some(&Answer::Text("CMZ".to_string())),
+            answers.get(5, Part::One)
+        );
+        assert_eq!(None, answers.get(5, Part::Two));
+        assert_eq!(None, answers.get(2, Part::One));
+    }
+
+    #[test]
+    fn handles_invalid_keys_and_values() {
+        assert_eq!(
+            Err(Error::parse("invalid day key 'first'")),
+            Answers::parse("[first]\npart1 = 1\n")
+        );
+        assert_eq!(
+            Err(Error::parse("invalid part key 'day1.part3'")),
+            Answers::parse("[day1]\npart3 = 1\n")
+        );
+        assert_eq!(
+            Err(Error::parse("expected table for key 'day1'")),
+            Answers::parse("day1 = 1\n")
+        );
+        assert_eq!(
+            Err(Error::parse(
+                "expected integer or string for key 'day1.part1'"
+            )),
+            Answers::parse("[day1]\npart1 = 1.5\n")
+        );
+    }
+
+    #[test]
+    fn reports_position_of_syntax_errors() {
+        let result = Answers::parse("[day1]\npart1 = \n");
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: Some(2),
+                column: Some(_),
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+use std::{fmt, time::Duration};
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::solver::{self, Part, Solver};
+use crate::timing;
+
+#[derive(PartialEq, Debug)]
+pub struct Benchmark {
+    pub day: u32,
+    pub part: Part,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    /// Median time spent parsing the input
+    pub parse: Duration,
+    /// Median time spent solving the parsed input
+    pub solve: Duration,
+}
+
+impl Benchmark {
+    pub fn run(
+        day: u32,
+        part: Part,
+        solver: &dyn Solver,
+        input: &str,
+        runs: usize,
+    ) -> Result<Benchmark, Error> {
+        let mut totals = Vec::with_capacity(runs);
+        let mut parses = Vec::with_capacity(runs);
+        let mut solves = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            let (result, timings) =
+                timing::measure(|| solver::solve_guarded(solver, part, &mut input.as_bytes()));
+            result?;
+
+            totals.push(timings.total());
+            parses.push(timings.parse);
+            solves.push(timings.solve);
+        }
+
+        totals.sort();
+        parses.sort();
+        solves.sort();
+
+        Ok(Benchmark {
+            day,
+            part,
+            min: totals.first().copied().unwrap_or_default(),
+            median: median(&totals),
+            max: totals.last().copied().unwrap_or_default(),
+            parse: median(&parses),
+            solve: median(&solves),
+        })
+    }
+
+    pub fn header() -> String {
+        format!(
+            "{:>3} {:>4} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "Day", "Part", "Min", "Median", "Max", "Parse", "Solve"
+        )
+    }
+}
+
+impl fmt::Display for Benchmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>3} {:>4} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            self.day,
+            self.part.number(),
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.max),
+            format_duration(self.parse),
+            format_duration(self.solve)
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct Report {
+    runs: usize,
+    results: Vec<ReportEntry>,
+}
+
+#[derive(Serialize)]
+struct ReportEntry {
+    day: u32,
+    part: u8,
+    min_ns: u64,
+    median_ns: u64,
+    max_ns: u64,
+    parse_ns: u64,
+    solve_ns: u64,
+}
+
+/// Serializes benchmarks to pretty printed JSON, one field per line so that results diff well between commits.
+pub fn to_json(benchmarks: &[Benchmark], runs: usize) -> String {
+    let report = Report {
+        runs,
+        results: benchmarks
+            .iter()
+            .map(|x| ReportEntry {
+                day: x.day,
+                part: x.part.number(),
+                min_ns: x.min.as_nanos() as u64,
+                median_ns: x.median.as_nanos() as u64,
+                max_ns: x.max.as_nanos() as u64,
+                parse_ns: x.parse.as_nanos() as u64,
+                solve_ns: x.solve.as_nanos() as u64,
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&report).unwrap()
+}
+
+fn median(sorted: &[Duration]) -> Duration {
+    match sorted.len() {
+        0 => Duration::ZERO,
+        len if len % 2 == 0 => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+        len => sorted[len / 2],
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.1}µs", duration.as_secs_f64() * 1_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use super::*;
+    use crate::registry::Registry;
+    use crate::solver::Answer;
+
+    #[test]
+    fn calculates_median() {
+        let ms = Duration::from_millis;
+
+        assert_eq!(Duration::ZERO, median(&[]));
+        assert_eq!(ms(2), median(&[ms(1), ms(2), ms(10)]));
+        assert_eq!(ms(3), median(&[ms(1), ms(2), ms(4), ms(10)]));
+    }
+
+    #[test]
+    fn benchmarks_solver() {
+        let registry = Registry::new();
+        let benchmark = Benchmark::run(
+            6,
+            Part::Two,
+            registry.get(6).unwrap(),
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            5,
+        )
+        .unwrap();
+
+        assert_eq!(6, benchmark.day);
+        assert_eq!(Part::Two, benchmark.part);
+        assert!(benchmark.min <= benchmark.median);
+        assert!(benchmark.median <= benchmark.max);
+    }
+
+    #[test]
+    fn returns_solver_error() {
+        let registry = Registry::new();
+        let result = Benchmark::run(6, Part::One, registry.get(6).unwrap(), "abab", 5);
+
+        assert_eq!(
+            Err(Error::NoSolution(
+                "start-of-packet marker not found".to_string()
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn returns_panic_as_error() {
+        struct Panicking;
+
+        impl Solver for Panicking {
+            fn part1(&self, _: &mut dyn BufRead) -> Result<Answer, Error> {
+                panic!("part 1 is broken")
+            }
+
+            fn part2(&self, _: &mut dyn BufRead) -> Result<Answer, Error> {
+                Ok(Answer::Number(2))
+            }
+        }
+
+        assert_eq!(
+            Err(Error::Panic("part 1 is broken".to_string())),
+            Benchmark::run(1, Part::One, &Panicking, "", 5)
+        );
+        assert!(Benchmark::run(1, Part::Two, &Panicking, "", 5).is_ok());
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let benchmark = Benchmark {
+            day: 7,
+            part: Part::One,
+            min: Duration::from_nanos(1),
+            median: Duration::from_nanos(2),
+            max: Duration::from_nanos(3),
+            parse: Duration::from_nanos(1),
+            solve: Duration::from_nanos(1),
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&[benchmark], 3)).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "runs": 3,
+                "results": [{
+                    "day": 7,
+                    "part": 1,
+                    "min_ns": 1,
+                    "median_ns": 2,
+                    "max_ns": 3,
+                    "parse_ns": 1,
+                    "solve_ns": 1
+                }]
+            }),
+            json
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::solver::{Answer, Part};
+
+pub const DEFAULT_CACHE_PATH: &str = ".aoc-cache.json";
+
+/// Version of the file format, caches of other versions are discarded.
+const FORMAT_VERSION: u32 = 1;
+
+/// Identifies an answer: the same solver version on the same input always gives it.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Serialize, Deserialize)]
+pub struct Key {
+    pub day: u32,
+    pub part: u8,
+    pub input_hash: String,
+    pub solver_version: String,
+}
+
+impl Key {
+    pub fn new(day: u32, part: Part, input: &str, solver_version: &str) -> Self {
+        Key {
+            day,
+            part: part.number(),
+            input_hash: hash(input),
+            solver_version: solver_version.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct File {
+    version: u32,
+    entries: Vec<Entry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    #[serde(flatten)]
+    key: Key,
+    answer: Answer,
+}
+
+/// Answers stored on disk between runs. It's shared by the worker threads when days run in parallel.
+pub struct Cache {
+    path: PathBuf,
+    answers: Mutex<BTreeMap<Key, Answer>>,
+}
+
+impl Cache {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Cache {
+            path: path.as_ref().to_path_buf(),
+            answers: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Loads the cache at `path`, which is empty if there's no file yet or it was written by another version.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Cache, Error> {
+        let result = Cache::new(path);
+        let content = match fs::read_to_string(&result.path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(result),
+            Err(error) => return Err(error.into()),
+        };
+
+        let file: File = serde_json::from_str(&content).map_err(|e| {
+            Error::parse(e.to_string())
+                .at_line(e.line())
+                .at_column(e.column())
+        })?;
+        if file.version == FORMAT_VERSION {
+            *result.answers.lock().unwrap() = file
+                .entries
+                .into_iter()
+                .map(|x| (x.key, x.answer))
+                .collect();
+        }
+
+        Ok(result)
+    }
+
+    pub fn get(&self, key: &Key) -> Option<Answer> {
+        self.answers.lock().unwrap().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: Key, answer: Answer) {
+        self.answers.lock().unwrap().insert(key, answer);
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let file = File {
+            version: FORMAT_VERSION,
+            entries: self
+                .answers
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(key, answer)| Entry {
+                    key: key.clone(),
+                    answer: answer.clone(),
+                })
+                .collect(),
+        };
+        fs::write(&self.path, serde_json::to_string_pretty(&file).unwrap())?;
+
+        Ok(())
+    }
+}
+
+/// 64-bit FNV-1a hash of `input` in hex. Unlike the hasher of the standard library it's stable between releases.
+pub fn hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, x| {
+        (hash ^ x as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc2022-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn hashes_input() {
+        assert_eq!("cbf29ce484222325", hash(""));
+        assert_eq!("af63dc4c8601ec8c", hash("a"));
+        assert_ne!(hash("1000\n2000\n"), hash("1000\n2001\n"));
+    }
+
+    #[test]
+    fn distinguishes_keys() {
+        let cache = Cache::new(temp_path("keys"));
+        cache.insert(Key::new(1, Part::One, "1\n", "1"), Answer::Number(1));
+
+        assert_eq!(
+            Some(Answer::Number(1)),
+            cache.get(&Key::new(1, Part::One, "1\n", "1"))
+        );
+        assert_eq!(None, cache.get(&Key::new(1, Part::Two, "1\n", "1")));
+        assert_eq!(None, cache.get(&Key::new(1, Part::One, "2\n", "1")));
+        assert_eq!(None, cache.get(&Key::new(1, Part::One, "1\n", "2")));
+    }
+
+    #[test]
+    fn saves_and_loads_answers() {
+        let path = temp_path("roundtrip");
+        let cache = Cache::new(&path);
+        cache.insert(Key::new(1, Part::One, "1\n", "1"), Answer::Number(24000));
+        cache.insert(
+            Key::new(5, Part::Two, "[A]\n", "1"),
+            "MCD".to_string().into(),
+        );
+        cache.save().unwrap();
+
+        let loaded = Cache::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            Some(Answer::Number(24000)),
+            loaded.get(&Key::new(1, Part::One, "1\n", "1"))
+        );
+        assert_eq!(
+            Some(Answer::Text("MCD".to_string())),
+            loaded.get(&Key::new(5, Part::Two, "[A]\n", "1"))
+        );
+    }
+
+    #[test]
+    fn starts_empty_without_file() {
+        let cache = Cache::load(temp_path("missing")).unwrap();
+
+        assert_eq!(None, cache.get(&Key::new(1, Part::One, "", "1")));
+    }
+
+    #[test]
+    fn discards_other_versions() {
+        let path = temp_path("version");
+        fs::write(
+            &path,
+            r#"{"version": 0, "entries": [{"day": 1, "part": 1,
+                "input_hash": "cbf29ce484222325", "solver_version": "1", "answer": 1}]}"#,
+        )
+        .unwrap();
+
+        let cache = Cache::load(&path).unwrap();
+        fs::write(&path, "{").unwrap();
+        let malformed = Cache::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(None, cache.get(&Key::new(1, Part::One, "", "1")));
+        assert!(matches!(malformed, Err(Error::Parse { .. })));
+    }
+}
@@ -0,0 +1,769 @@
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+
+use aoc2022::client::{self, Client};
+use aoc2022::config::{self, Config};
+use aoc2022::error::Error;
+use aoc2022::logging;
+use aoc2022::registry;
+use aoc2022::solver::Part;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Advent of Code 2022 solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    #[command(flatten)]
+    pub config: ConfigArgs,
+
+    #[command(flatten)]
+    pub log: LogArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    /// Configuration file with puzzle constants and input paths, defaults to aoc.toml if it exists
+    #[arg(long, global = true)]
+    pub config: Option<String>,
+
+    /// Override a configuration value, e.g. `day7.disk_size=80000000`
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    pub overrides: Vec<String>,
+}
+
+impl ConfigArgs {
+    pub fn path(&self) -> &str {
+        self.config
+            .as_deref()
+            .unwrap_or(config::DEFAULT_CONFIG_PATH)
+    }
+
+    pub fn load(&self) -> Result<Config, Error> {
+        Config::load(self.config.as_deref(), &self.overrides)
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct LogArgs {
+    /// Log events to stderr, filtered by level and span, e.g. `debug` or `[day{day=7}]=trace`
+    #[arg(long = "log", value_name = "FILTER", env = logging::LOG_VARIABLE, global = true)]
+    pub filter: Option<String>,
+
+    /// Format of the logged events
+    #[arg(long, value_enum, default_value_t = LogFormat::Text, global = true)]
+    pub log_format: LogFormat,
+}
+
+#[derive(ValueEnum, PartialEq, Debug, Clone, Copy)]
+pub enum LogFormat {
+    /// One line per event, prefixed by its spans
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+impl LogArgs {
+    /// Starts logging if a filter is given.
+    pub fn init(&self) -> Result<(), Error> {
+        let Some(filter) = &self.filter else {
+            return Ok(());
+        };
+        let format = match self.log_format {
+            LogFormat::Text => logging::Format::Text,
+            LogFormat::Json => logging::Format::Json,
+        };
+
+        logging::init(filter, format)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run solvers and print their answers
+    Run(RunArgs),
+    /// Run solvers against their inputs and compare answers with the expected ones
+    Verify(VerifyArgs),
+    /// Run solvers repeatedly and report their timings
+    Bench(BenchArgs),
+    /// Re-run a day whenever its input or example changes
+    Watch(WatchArgs),
+    /// Generate the module, registration and fixture files of a new day
+    NewDay(NewDayArgs),
+    /// Generate a random input for a day
+    Generate(GenerateArgs),
+    /// Check that alternative implementations agree with the solvers on real and generated inputs
+    Differential(DifferentialArgs),
+    /// Serve the solvers over HTTP, `POST /day/{n}/part/{p}` with the input as the body
+    Serve(ServeArgs),
+    /// Download a day's puzzle input to its configured path, unless it's already there
+    Fetch(FetchArgs),
+    /// Submit an answer and report whether it's right
+    Submit(SubmitArgs),
+    /// Import the example input and answers of a saved puzzle page into examples/
+    Import(ImportArgs),
+    /// Run solvers and write a Markdown or HTML report of their answers, timings and verification
+    Report(ReportArgs),
+}
+
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("selection").required(true).args(["day", "all"])))]
+pub struct RunArgs {
+    /// Day to run
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: Option<u32>,
+
+    /// Run all registered days
+    #[arg(long)]
+    pub all: bool,
+
+    /// Part to run, both parts are run if omitted
+    #[arg(short, long, value_parser = parse_part)]
+    pub part: Option<Part>,
+
+    /// Input file or `-` for stdin, defaults to the configured input of the day
+    #[arg(short, long, conflicts_with = "all")]
+    pub input: Option<String>,
+
+    /// Output format of the answers
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// Number of days to run concurrently
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: u32,
+
+    /// Print a step-by-step account of how each answer is reached
+    #[arg(short, long)]
+    pub explain: bool,
+
+    /// Solve every part instead of reusing answers cached for the same input and solver
+    #[arg(long)]
+    pub no_cache: bool,
+}
+
+#[derive(ValueEnum, PartialEq, Debug, Clone, Copy)]
+pub enum Format {
+    /// One line per day and part
+    Text,
+    /// Single JSON document with answers, their types, elapsed times and errors
+    Json,
+}
+
+impl RunArgs {
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Day to verify, all registered days are verified if omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: Option<u32>,
+
+    /// Manifest with expected answers, defaults to inputs/answers.toml
+    #[arg(short, long)]
+    pub answers: Option<String>,
+
+    /// Verify against the checked-in example inputs and answers instead
+    #[arg(short, long, conflicts_with = "answers")]
+    pub examples: bool,
+
+    /// Number of days to run concurrently
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: u32,
+}
+
+impl VerifyArgs {
+    pub fn answers_path(&self) -> String {
+        match &self.answers {
+            Some(path) => path.clone(),
+            None if self.examples => registry::EXAMPLE_ANSWERS_PATH.to_string(),
+            None => registry::DEFAULT_ANSWERS_PATH.to_string(),
+        }
+    }
+
+    pub fn input_path(&self, config: &Config, day: u32) -> String {
+        if self.examples {
+            registry::example_input_path(day)
+        } else {
+            config.input_path(day)
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Day to benchmark, all registered days are benchmarked if omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: Option<u32>,
+
+    /// Part to benchmark, both parts are benchmarked if omitted
+    #[arg(short, long, value_parser = parse_part)]
+    pub part: Option<Part>,
+
+    /// Number of runs per day and part
+    #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+
+    /// JSON file to write the results to
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+impl BenchArgs {
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    /// Day to watch
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: u32,
+
+    /// Part to run, both parts are run if omitted
+    #[arg(short, long, value_parser = parse_part)]
+    pub part: Option<Part>,
+
+    /// Milliseconds between checks for changes
+    #[arg(long, default_value_t = 500, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+}
+
+impl WatchArgs {
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+
+    pub fn paths(&self, config: &Config) -> Vec<String> {
+        vec![
+            config.input_path(self.day),
+            registry::example_input_path(self.day),
+        ]
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct NewDayArgs {
+    /// Day to generate
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: u32,
+}
+
+#[derive(Args, Debug)]
+pub struct GenerateArgs {
+    /// Day to generate the input for
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: u32,
+
+    /// Size of the input, e.g. the number of lines or directories
+    #[arg(short, long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+    pub size: u64,
+
+    /// Seed of the random generator, a random one is picked and reported if omitted
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// File to write the input to, defaults to stdout
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DifferentialArgs {
+    /// Day to check, all registered days are checked if omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: Option<u32>,
+
+    /// Number of generated inputs per day
+    #[arg(short, long, default_value_t = 100)]
+    pub runs: u32,
+
+    /// Largest size of the generated inputs
+    #[arg(short, long, default_value_t = 50, value_parser = clap::value_parser!(u64).range(1..))]
+    pub size: u64,
+
+    /// Seed of the first generated input, a random one is picked and reported if omitted
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:3000")]
+    pub address: String,
+
+    /// Number of requests to answer concurrently, further connections wait for a free worker
+    #[arg(short, long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: u32,
+}
+
+#[derive(Args, Debug)]
+pub struct EndpointArgs {
+    /// Session token, the value of the `session` cookie of a logged in browser
+    #[arg(long, env = client::SESSION_VARIABLE, hide_env_values = true)]
+    pub session: Option<String>,
+
+    /// Site to talk to, e.g. a local stub server
+    #[arg(long, env = client::BASE_URL_VARIABLE, default_value = client::DEFAULT_BASE_URL)]
+    pub base_url: String,
+}
+
+impl EndpointArgs {
+    pub fn client(&self) -> Result<Client, String> {
+        match &self.session {
+            Some(session) => Ok(Client::new(&self.base_url, session)),
+            None => Err(format!(
+                "session token required, pass --session or set {}",
+                client::SESSION_VARIABLE
+            )),
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct FetchArgs {
+    /// Day to fetch
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: u32,
+
+    /// Fetch again even if the input was already downloaded
+    #[arg(long)]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub endpoint: EndpointArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    /// Day to submit
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: u32,
+
+    /// Part to submit
+    #[arg(short, long, value_parser = parse_part)]
+    pub part: Part,
+
+    /// Answer to submit, defaults to solving the configured input of the day
+    #[arg(short, long)]
+    pub answer: Option<String>,
+
+    #[command(flatten)]
+    pub endpoint: EndpointArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// Saved HTML page of the puzzle
+    pub page: String,
+
+    /// Day of the puzzle, taken from the page title if omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: Option<u32>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReportArgs {
+    /// Day to report on, all registered days are reported on if omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: Option<u32>,
+
+    /// Manifest with expected answers, defaults to inputs/answers.toml
+    #[arg(short, long)]
+    pub answers: Option<String>,
+
+    /// Report on the checked-in example inputs and answers instead
+    #[arg(short, long, conflicts_with = "answers")]
+    pub examples: bool,
+
+    /// Format of the report
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Markdown)]
+    pub format: ReportFormat,
+
+    /// File to write the report to, defaults to stdout
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Number of days to run concurrently
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: u32,
+}
+
+#[derive(ValueEnum, PartialEq, Debug, Clone, Copy)]
+pub enum ReportFormat {
+    Markdown,
+    /// Standalone page with inline styles
+    Html,
+}
+
+impl ReportArgs {
+    pub fn answers_path(&self) -> String {
+        match &self.answers {
+            Some(path) => path.clone(),
+            None if self.examples => registry::EXAMPLE_ANSWERS_PATH.to_string(),
+            None => registry::DEFAULT_ANSWERS_PATH.to_string(),
+        }
+    }
+
+    pub fn input_path(&self, config: &Config, day: u32) -> String {
+        if self.examples {
+            registry::example_input_path(day)
+        } else {
+            config.input_path(day)
+        }
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    let number: u8 = value
+        .parse()
+        .map_err(|_| format!("invalid part '{}'", value))?;
+    Part::try_from(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn has_valid_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_single_day_run() {
+        let cli = Cli::try_parse_from([
+            "aoc2022",
+            "run",
+            "--day",
+            "7",
+            "--part",
+            "2",
+            "--input",
+            "other.txt",
+        ])
+        .unwrap();
+
+        let Command::Run(args) = cli.command else {
+            panic!("expected run command");
+        };
+        assert_eq!(Some(7), args.day);
+        assert!(!args.all);
+        assert_eq!(vec![Part::Two], args.parts());
+        assert_eq!(Some("other.txt".to_string()), args.input);
+        assert_eq!(Format::Text, args.format);
+        assert_eq!(1, args.jobs);
+        assert!(!args.explain);
+        assert!(!args.no_cache);
+    }
+
+    #[test]
+    fn parses_all_days_run() {
+        let cli = Cli::try_parse_from(["aoc2022", "run", "--all"]).unwrap();
+
+        let Command::Run(args) = cli.command else {
+            panic!("expected run command");
+        };
+        assert_eq!(None, args.day);
+        assert!(args.all);
+        assert_eq!(vec![Part::One, Part::Two], args.parts());
+    }
+
+    #[test]
+    fn parses_no_cache() {
+        let cli = Cli::try_parse_from(["aoc2022", "run", "--all", "--no-cache"]).unwrap();
+
+        let Command::Run(args) = cli.command else {
+            panic!("expected run command");
+        };
+        assert!(args.no_cache);
+    }
+
+    #[test]
+    fn parses_explain() {
+        let cli = Cli::try_parse_from(["aoc2022", "run", "--day", "5", "--explain"]).unwrap();
+
+        let Command::Run(args) = cli.command else {
+            panic!("expected run command");
+        };
+        assert!(args.explain);
+    }
+
+    #[test]
+    fn parses_json_format() {
+        let cli = Cli::try_parse_from(["aoc2022", "run", "--all", "--format", "json"]).unwrap();
+
+        let Command::Run(args) = cli.command else {
+            panic!("expected run command");
+        };
+        assert_eq!(Format::Json, args.format);
+    }
+
+    #[test]
+    fn parses_verify() {
+        let cli = Cli::try_parse_from(["aoc2022", "verify"]).unwrap();
+
+        let Command::Verify(args) = cli.command else {
+            panic!("expected verify command");
+        };
+        assert_eq!(None, args.day);
+        assert_eq!("inputs/answers.toml", args.answers_path());
+        assert_eq!("inputs/day3.txt", args.input_path(&Config::default(), 3));
+    }
+
+    #[test]
+    fn parses_verify_with_examples() {
+        let cli = Cli::try_parse_from(["aoc2022", "verify", "--examples", "--jobs", "4"]).unwrap();
+
+        let Command::Verify(args) = cli.command else {
+            panic!("expected verify command");
+        };
+        assert_eq!("examples/answers.toml", args.answers_path());
+        assert_eq!("examples/day3.txt", args.input_path(&Config::default(), 3));
+        assert_eq!(4, args.jobs);
+    }
+
+    #[test]
+    fn parses_bench() {
+        let cli = Cli::try_parse_from([
+            "aoc2022",
+            "bench",
+            "--day",
+            "8",
+            "--runs",
+            "5",
+            "--output",
+            "bench.json",
+        ])
+        .unwrap();
+
+        let Command::Bench(args) = cli.command else {
+            panic!("expected bench command");
+        };
+        assert_eq!(Some(8), args.day);
+        assert_eq!(vec![Part::One, Part::Two], args.parts());
+        assert_eq!(5, args.runs);
+        assert_eq!(Some("bench.json".to_string()), args.output);
+    }
+
+    #[test]
+    fn parses_watch() {
+        let cli = Cli::try_parse_from(["aoc2022", "watch", "--day", "5"]).unwrap();
+
+        let Command::Watch(args) = cli.command else {
+            panic!("expected watch command");
+        };
+        assert_eq!(5, args.day);
+        assert_eq!(vec![Part::One, Part::Two], args.parts());
+        assert_eq!(500, args.interval);
+        assert_eq!(
+            vec![
+                "inputs/day5.txt".to_string(),
+                "examples/day5.txt".to_string()
+            ],
+            args.paths(&Config::default())
+        );
+    }
+
+    #[test]
+    fn parses_new_day() {
+        let cli = Cli::try_parse_from(["aoc2022", "new-day", "9"]).unwrap();
+
+        let Command::NewDay(args) = cli.command else {
+            panic!("expected new-day command");
+        };
+        assert_eq!(9, args.day);
+    }
+
+    #[test]
+    fn parses_generate() {
+        let cli =
+            Cli::try_parse_from(["aoc2022", "generate", "--day", "3", "--seed", "7"]).unwrap();
+
+        let Command::Generate(args) = cli.command else {
+            panic!("expected generate command");
+        };
+        assert_eq!(3, args.day);
+        assert_eq!(100, args.size);
+        assert_eq!(Some(7), args.seed);
+        assert_eq!(None, args.output);
+    }
+
+    #[test]
+    fn parses_differential() {
+        let cli =
+            Cli::try_parse_from(["aoc2022", "differential", "-d", "6", "--runs", "5"]).unwrap();
+
+        let Command::Differential(args) = cli.command else {
+            panic!("expected differential command");
+        };
+        assert_eq!(Some(6), args.day);
+        assert_eq!(5, args.runs);
+        assert_eq!(50, args.size);
+        assert_eq!(None, args.seed);
+    }
+
+    #[test]
+    fn parses_serve() {
+        let cli = Cli::try_parse_from(["aoc2022", "serve"]).unwrap();
+
+        let Command::Serve(args) = cli.command else {
+            panic!("expected serve command");
+        };
+        assert_eq!("127.0.0.1:3000", args.address);
+        assert_eq!(4, args.jobs);
+    }
+
+    #[test]
+    fn parses_fetch() {
+        let cli = Cli::try_parse_from([
+            "aoc2022",
+            "fetch",
+            "--day",
+            "3",
+            "--session",
+            "secret",
+            "--base-url",
+            "http://localhost:8000",
+        ])
+        .unwrap();
+
+        let Command::Fetch(args) = cli.command else {
+            panic!("expected fetch command");
+        };
+        assert_eq!(3, args.day);
+        assert!(!args.force);
+        assert_eq!(Some("secret".to_string()), args.endpoint.session);
+        assert_eq!("http://localhost:8000", args.endpoint.base_url);
+    }
+
+    #[test]
+    fn parses_submit() {
+        let cli =
+            Cli::try_parse_from(["aoc2022", "submit", "-d", "6", "-p", "2", "-a", "19"]).unwrap();
+
+        let Command::Submit(args) = cli.command else {
+            panic!("expected submit command");
+        };
+        assert_eq!(6, args.day);
+        assert_eq!(Part::Two, args.part);
+        assert_eq!(Some("19".to_string()), args.answer);
+    }
+
+    #[test]
+    fn parses_import() {
+        let cli = Cli::try_parse_from(["aoc2022", "import", "day5.html"]).unwrap();
+
+        let Command::Import(args) = cli.command else {
+            panic!("expected import command");
+        };
+        assert_eq!("day5.html", args.page);
+        assert_eq!(None, args.day);
+    }
+
+    #[test]
+    fn parses_report() {
+        let cli = Cli::try_parse_from([
+            "aoc2022",
+            "report",
+            "--examples",
+            "--format",
+            "html",
+            "-o",
+            "report.html",
+        ])
+        .unwrap();
+
+        let Command::Report(args) = cli.command else {
+            panic!("expected report command");
+        };
+        assert_eq!(None, args.day);
+        assert_eq!(ReportFormat::Html, args.format);
+        assert_eq!(Some("report.html".to_string()), args.output);
+        assert_eq!("examples/answers.toml", args.answers_path());
+        assert_eq!("examples/day8.txt", args.input_path(&Config::default(), 8));
+    }
+
+    #[test]
+    fn parses_config_overrides() {
+        let cli = Cli::try_parse_from([
+            "aoc2022",
+            "run",
+            "--day",
+            "7",
+            "--config",
+            "other.toml",
+            "--set",
+            "day7.disk_size=80000000",
+            "--set",
+            "inputs.directory=data",
+        ])
+        .unwrap();
+
+        assert_eq!("other.toml", cli.config.path());
+        assert_eq!(
+            vec![
+                "day7.disk_size=80000000".to_string(),
+                "inputs.directory=data".to_string()
+            ],
+            cli.config.overrides
+        );
+    }
+
+    #[test]
+    fn parses_log_options() {
+        let cli = Cli::try_parse_from([
+            "aoc2022",
+            "verify",
+            "--log",
+            "debug",
+            "--log-format",
+            "json",
+        ])
+        .unwrap();
+
+        assert_eq!(Some("debug".to_string()), cli.log.filter);
+        assert_eq!(LogFormat::Json, cli.log.log_format);
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        // Either a day or --all has to be selected
+        assert!(Cli::try_parse_from(["aoc2022", "run"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "run", "--day", "1", "--all"]).is_err());
+
+        // Custom input only makes sense for a single day
+        assert!(Cli::try_parse_from(["aoc2022", "run", "--all", "--input", "a.txt"]).is_err());
+
+        assert!(Cli::try_parse_from(["aoc2022", "run", "--day", "26"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "run", "--day", "1", "--part", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "bench", "--runs", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "run", "--all", "--jobs", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "watch"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "new-day", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "generate", "-d", "1", "--size", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "run", "--all", "--format", "xml"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "differential", "--size", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "fetch"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "submit", "--day", "1"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "import"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "report", "-e", "-a", "a.toml"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "verify", "--log-format", "xml"]).is_err());
+        assert!(Cli::try_parse_from(["aoc2022", "serve", "--jobs", "0"]).is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use std::{fmt, fs, path::Path, time::Duration};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use ureq::Agent;
+
+use crate::error::Error;
+use crate::solver::Part;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+pub const BASE_URL_VARIABLE: &str = "AOC_BASE_URL";
+
+const YEAR: u32 = 2022;
+/// The site asks automated tools to identify themselves.
+const USER_AGENT: &str = "github.com/mchwalek/aoc2022";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+lazy_static! {
+    static ref ARTICLE_REGEX: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    static ref TAG_REGEX: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref WHITESPACE_REGEX: Regex = Regex::new(r"\s+").unwrap();
+    static ref HINT_REGEX: Regex = Regex::new(r"your answer is (too (?:high|low))").unwrap();
+    static ref WAIT_REGEX: Regex = Regex::new(r"You have (.+?) left to wait").unwrap();
+}
+
+/// Outcome of submitting an answer, as told by the response page.
+#[derive(PartialEq, Debug)]
+pub enum Submission {
+    Correct,
+    /// The site sometimes tells whether the answer was too high or too low.
+    Incorrect {
+        hint: Option<String>,
+    },
+    /// Answers are rate limited, `wait` is how long until the next one is accepted, e.g. `4m 12s`.
+    TooSoon {
+        wait: Option<String>,
+    },
+    /// The part was already solved, or the previous one wasn't yet.
+    AlreadySolved,
+    /// Text of a response that none of the above match.
+    Unknown(String),
+}
+
+impl Submission {
+    pub fn parse(html: &str) -> Self {
+        let text = ARTICLE_REGEX
+            .captures(html)
+            .map_or(html, |x| x.get(1).unwrap().as_str());
+        let text = TAG_REGEX.replace_all(text, "");
+        let text = WHITESPACE_REGEX.replace_all(&text, " ").trim().to_string();
+
+        if text.contains("That's the right answer") {
+            Submission::Correct
+        } else if text.contains("That's not the right answer") {
+            Submission::Incorrect {
+                hint: HINT_REGEX.captures(&text).map(|x| x[1].to_string()),
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Submission::TooSoon {
+                wait: WAIT_REGEX.captures(&text).map(|x| x[1].to_string()),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Submission::AlreadySolved
+        } else {
+            Submission::Unknown(text)
+        }
+    }
+
+    pub fn is_correct(&self) -> bool {
+        matches!(self, Submission::Correct)
+    }
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Submission::Correct => write!(f, "right answer"),
+            Submission::Incorrect { hint: Some(hint) } => write!(f, "wrong answer, {}", hint),
+            Submission::Incorrect { hint: None } => write!(f, "wrong answer"),
+            Submission::TooSoon { wait: Some(wait) } => {
+                write!(f, "answered too recently, wait {}", wait)
+            }
+            Submission::TooSoon { wait: None } => write!(f, "answered too recently"),
+            Submission::AlreadySolved => write!(f, "already solved"),
+            Submission::Unknown(text) => write!(f, "unrecognized response '{}'", text),
+        }
+    }
+}
+
+/// Client of the puzzle site, or of anything serving the same endpoints under `base_url`.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(TIMEOUT))
+            .build()
+            .into();
+
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    fn url(&self, day: u32, endpoint: &str) -> String {
+        format!("{}/{}/day/{}/{}", self.base_url, YEAR, day, endpoint)
+    }
+
+    pub fn fetch_input(&self, day: u32) -> Result<String, Error> {
+        let url = self.url(day, "input");
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call();
+
+        read_body(&url, response)
+    }
+
+    pub fn submit(&self, day: u32, part: Part, answer: &str) -> Result<Submission, Error> {
+        let url = self.url(day, "answer");
+        let level = part.number().to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", level.as_str()), ("answer", answer)]);
+
+        read_body(&url, response).map(|x| Submission::parse(&x))
+    }
+}
+
+fn read_body(
+    url: &str,
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String, Error> {
+    let mut response = response.map_err(|x| Error::Http(format!("{}: {}", url, x)))?;
+    let status = response.status();
+    if status != 200 {
+        return Err(Error::Http(format!("{}: status {}", url, status)));
+    }
+
+    response
+        .body_mut()
+        .read_to_string()
+        .map_err(|x| Error::Http(format!("{}: {}", url, x)))
+}
+
+/// Whether `path` holds a previously fetched input. New days are scaffolded with an empty one, which doesn't count.
+pub fn is_fetched(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|x| x.len() > 0)
+}
+
+/// Downloads the input of `day` to `path`, creating its directory if needed. The input is written next to it first
+/// and then renamed, so that an interrupted write doesn't leave a partial input that `is_fetched` accepts.
+pub fn fetch_to(client: &Client, day: u32, path: &Path) -> Result<(), Error> {
+    let input = client.fetch_input(day)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    fs::write(&temp_path, input)?;
+    fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        io::{BufReader, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    use super::*;
+    use crate::server::{self, Request};
+
+    /// Answers one request per response with a canned page, passing the requests on for inspection.
+    fn start_stub(responses: Vec<(u16, &'static str)>) -> (String, Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = server::read_request(BufReader::new(&stream)).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                sender.send(request).unwrap();
+            }
+        });
+
+        (format!("http://{}", address), receiver)
+    }
+
+    fn header<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+        request
+            .headers
+            .iter()
+            .find(|(x, _)| x == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (base_url, requests) = start_stub(vec![(200, "1000\n2000\n")]);
+        let client = Client::new(&base_url, "secret");
+
+        assert_eq!("1000\n2000\n", client.fetch_input(1).unwrap());
+
+        let request = requests.recv().unwrap();
+        assert_eq!("GET", request.method);
+        assert_eq!("/2022/day/1/input", request.path);
+        assert_eq!(Some("session=secret"), header(&request, "cookie"));
+    }
+
+    #[test]
+    fn fails_on_error_status() {
+        let (base_url, _requests) = start_stub(vec![(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        )]);
+        let client = Client::new(&base_url, "expired");
+
+        let error = client.fetch_input(1).unwrap_err();
+        assert_eq!(
+            Error::Http(format!(
+                "{}/2022/day/1/input: status 400 Bad Request",
+                base_url
+            )),
+            error
+        );
+    }
+
+    #[test]
+    fn saves_fetched_input() {
+        let (base_url, _requests) = start_stub(vec![(200, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n")]);
+        let client = Client::new(&base_url, "secret");
+        let path = env::temp_dir()
+            .join(format!("aoc2022-fetch-{}", std::process::id()))
+            .join("day6.txt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "").unwrap();
+
+        assert!(!is_fetched(&path));
+        fetch_to(&client, 6, &path).unwrap();
+        assert!(is_fetched(&path));
+        assert_eq!(
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n",
+            fs::read_to_string(&path).unwrap()
+        );
+        assert!(!path.with_extension("txt.tmp").exists());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (base_url, requests) = start_stub(vec![(
+            200,
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        )]);
+        let client = Client::new(&base_url, "secret");
+
+        assert_eq!(
+            Submission::Correct,
+            client.submit(6, Part::Two, "19").unwrap()
+        );
+
+        let request = requests.recv().unwrap();
+        assert_eq!("POST", request.method);
+        assert_eq!("/2022/day/6/answer", request.path);
+        assert_eq!(b"level=2&answer=19".to_vec(), request.body);
+    }
+
+    #[test]
+    fn parses_submission_outcomes() {
+        assert_eq!(
+            Submission::Incorrect {
+                hint: Some("too high".to_string())
+            },
+            Submission::parse(
+                "<article><p>That's not the right answer; your answer is too high. \
+                 Please wait one minute before trying again.</p></article>"
+            )
+        );
+        assert_eq!(
+            Submission::Incorrect { hint: None },
+            Submission::parse("<article><p>That's not the right answer.</p></article>")
+        );
+        assert_eq!(
+            Submission::TooSoon {
+                wait: Some("4m 12s".to_string())
+            },
+            Submission::parse(
+                "<article><p>You gave an answer too recently; you have to wait after submitting \
+                 an answer before trying again.  You have 4m 12s left to wait.</p></article>"
+            )
+        );
+        assert_eq!(
+            Submission::AlreadySolved,
+            Submission::parse(
+                "<article><p>You don't seem to be solving the right level.  \
+                 Did you already complete it?</p></article>"
+            )
+        );
+        assert_eq!(
+            Submission::Unknown("Something else happened.".to_string()),
+            Submission::parse(
+                "<html><article>\n<p>Something <em>else</em> happened.</p></article>"
+            )
+        );
+    }
+}
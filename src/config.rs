@@ -0,0 +1,226 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::Deserialize;
+use toml::{Table, Value};
+
+use crate::error::Error;
+use crate::{day1_part2, day6, day7};
+
+pub const DEFAULT_CONFIG_PATH: &str = "aoc.toml";
+
+/// Puzzle constants and input paths, loaded from a file such as:
+///
+/// ```toml
+/// [inputs]
+/// directory = "inputs"
+/// days = { day7 = "inputs/day7-large.txt" }
+///
+/// [day7]
+/// disk_size = 80000000
+/// ```
+///
+/// Everything is optional, the defaults are the values of the puzzles.
+#[derive(Deserialize, PartialEq, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub inputs: Inputs,
+    pub day1: day1_part2::Params,
+    pub day6: day6::Params,
+    pub day7: day7::Params,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Inputs {
+    /// Directory with the `dayN.txt` inputs.
+    pub directory: String,
+    /// Inputs of single days kept elsewhere, keyed by `dayN`.
+    pub days: BTreeMap<String, String>,
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Inputs {
+            directory: "inputs".to_string(),
+            days: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads the file at `path`, or `aoc.toml` if there is one, and applies the overrides on top of it.
+    pub fn load(path: Option<&str>, overrides: &[String]) -> Result<Config, Error> {
+        let content = match path {
+            Some(path) => fs::read_to_string(path)?,
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                fs::read_to_string(DEFAULT_CONFIG_PATH)?
+            }
+            None => String::new(),
+        };
+
+        Self::parse(&content, overrides)
+    }
+
+    /// Parses `content`, with overrides given as `key=value` and keys of nested tables joined by dots, e.g.
+    /// `day7.disk_size=80000000`. Values that aren't valid TOML are taken as strings.
+    pub fn parse(content: &str, overrides: &[String]) -> Result<Config, Error> {
+        let mut table: Table = content
+            .parse()
+            .map_err(|e: toml::de::Error| Error::toml(content, &e))?;
+        for item in overrides {
+            apply_override(&mut table, item)?;
+        }
+
+        let result: Config = Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| Error::parse(e.message()))?;
+        result.validate()?;
+
+        Ok(result)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.day6.packet_marker_size == 0 || self.day6.message_marker_size == 0 {
+            return Err(Error::parse("marker sizes of day6 must be at least 1"));
+        }
+        if let Some(key) = self.inputs.days.keys().find(|x| parse_day(x).is_none()) {
+            return Err(Error::parse(format!(
+                "invalid day key 'inputs.days.{}'",
+                key
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn input_path(&self, day: u32) -> String {
+        self.inputs
+            .days
+            .iter()
+            .find(|(key, _)| parse_day(key) == Some(day))
+            .map(|(_, path)| path.clone())
+            .unwrap_or_else(|| {
+                format!(
+                    "{}/day{}.txt",
+                    self.inputs.directory.trim_end_matches('/'),
+                    day
+                )
+            })
+    }
+}
+
+fn parse_day(key: &str) -> Option<u32> {
+    key.strip_prefix("day").and_then(|x| x.parse().ok())
+}
+
+fn apply_override(table: &mut Table, item: &str) -> Result<(), Error> {
+    let invalid = || Error::parse(format!("invalid override '{}', expected key=value", item));
+    let (key, value) = item.split_once('=').ok_or_else(invalid)?;
+    let keys: Vec<&str> = key.trim().split('.').collect();
+    if keys.iter().any(|x| x.is_empty()) {
+        return Err(invalid());
+    }
+
+    let value = format!("value = {}", value.trim())
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut x| x.remove("value"))
+        .unwrap_or_else(|| Value::String(value.trim().to_string()));
+
+    let (last, parents) = keys.split_last().unwrap();
+    let mut current = table;
+    for &key in parents {
+        current = current
+            .entry(key)
+            .or_insert_with(|| Value::Table(Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| {
+                Error::parse(format!(
+                    "key '{}' of override '{}' isn't a table",
+                    key, item
+                ))
+            })?;
+    }
+    current.insert(last.to_string(), value);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_puzzle_values_by_default() {
+        let config = Config::parse("", &[]).unwrap();
+
+        assert_eq!(Config::default(), config);
+        assert_eq!(3, config.day1.top_count);
+        assert_eq!(14, config.day6.message_marker_size);
+        assert_eq!(100000, config.day7.max_small_dir_size);
+        assert_eq!("inputs/day7.txt", config.input_path(7));
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            "[inputs]\ndirectory = \"data/\"\ndays = { day7 = \"big.txt\" }\n\n\
+             [day7]\ndisk_size = 80000000\n",
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(80000000, config.day7.disk_size);
+        assert_eq!(30000000, config.day7.required_free_space);
+        assert_eq!("big.txt", config.input_path(7));
+        assert_eq!("data/day6.txt", config.input_path(6));
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let config = Config::parse(
+            "[day1]\ntop_count = 5\n",
+            &[
+                "day1.top_count=2".to_string(),
+                "day6.packet_marker_size = 8".to_string(),
+                "inputs.directory=other".to_string(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(2, config.day1.top_count);
+        assert_eq!(8, config.day6.packet_marker_size);
+        assert_eq!("other/day1.txt", config.input_path(1));
+    }
+
+    #[test]
+    fn handles_invalid_config() {
+        assert!(matches!(
+            Config::parse("[day7]\ndisk_size = \n", &[]),
+            Err(Error::Parse { line: Some(2), .. })
+        ));
+        assert!(Config::parse("[day7]\ndisk = 1\n", &[]).is_err());
+        assert!(Config::parse("", &["day7.disk_size=big".to_string()]).is_err());
+        assert_eq!(
+            Err(Error::parse("marker sizes of day6 must be at least 1")),
+            Config::parse("", &["day6.message_marker_size=0".to_string()])
+        );
+        assert_eq!(
+            Err(Error::parse("invalid day key 'inputs.days.seven'")),
+            Config::parse("[inputs.days]\nseven = \"a.txt\"\n", &[])
+        );
+        assert_eq!(
+            Err(Error::parse("invalid override 'day7', expected key=value")),
+            Config::parse("", &["day7".to_string()])
+        );
+        assert_eq!(
+            Err(Error::parse(
+                "key 'day1' of override 'day1.top_count.x=1' isn't a table"
+            )),
+            Config::parse(
+                "",
+                &["day1=1".to_string(), "day1.top_count.x=1".to_string()]
+            )
+        );
+    }
+}
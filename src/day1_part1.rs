@@ -1,38 +1,72 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
-
-pub fn run(path: &str) -> i32 {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-
-    let mut max_sum = 0;
-    let mut sum = 0;
-    for line_result in reader.lines() {
-        let line = line_result.unwrap();
-        match line.parse::<i32>() {
-            Ok(calories) => sum += calories,
-            Err(_) => {
-                if sum > max_sum {
-                    max_sum = sum;
-                }
-
-                sum = 0;
-            }
-        }
+use std::io::BufRead;
+
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+use crate::trace;
+
+pub fn run(path: &str) -> Result<i32, Error> {
+    solve(input::open(path)?)
+}
+
+pub fn solve<R: BufRead>(reader: R) -> Result<i32, Error> {
+    let elves = parse_elves(reader)?;
+    timing::enter(Phase::Solve);
+
+    for (index, calories) in elves.iter().enumerate() {
+        trace::step(|| format!("elf {} carries {} calories", index + 1, calories));
     }
+    let Some((index, calories)) = elves.iter().enumerate().max_by_key(|(_, x)| **x) else {
+        return Ok(0);
+    };
+    trace::step(|| format!("elf {} carries the most calories", index + 1));
+
+    Ok(*calories)
+}
 
-    max_sum
+/// Total calories carried by every elf.
+fn parse_elves<R: BufRead>(reader: R) -> Result<Vec<i32>, Error> {
+    input::read_sections(reader)?
+        .iter()
+        .map(|x| {
+            x.parse_lines(|line| {
+                line.parse::<i32>()
+                    .map_err(|_| Error::parse(format!("invalid calories '{}'", line)))
+            })?
+            .into_iter()
+            .try_fold(0i32, |sum, x| sum.checked_add(x))
+            .ok_or_else(|| Error::parse("total calories of elf overflow").at_line(x.start))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn returns_error_on_invalid_calories() {
+        assert_eq!(
+            Err(Error::parse("invalid calories 'abc'").at_line(3)),
+            solve("1000\n\nabc\n".as_bytes())
+        );
+    }
+
+    #[test]
+    fn returns_error_on_overflow() {
+        assert_eq!(
+            Err(Error::parse("total calories of elf overflow").at_line(3)),
+            solve("1\n\n2147483647\n1\n".as_bytes())
+        );
+    }
+
+    #[test]
+    fn counts_last_elf() {
+        assert_eq!(Ok(5), solve("1\n\n2\n3\n".as_bytes()));
+    }
+
     #[test]
     fn returns_answer() {
-        let result = run("inputs/day1.txt");
-        println!("{}", result);
+        assert_eq!(Ok(24000), run("examples/day1.txt"));
     }
 }
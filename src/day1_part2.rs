@@ -1,38 +1,97 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
-
-pub fn run(path: &str) -> i32 {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-
-    let mut sums: Vec<i32> = Vec::new();
-    let mut sum = 0;
-    for line_result in reader.lines() {
-        let line = line_result.unwrap();
-        match line.parse::<i32>() {
-            Ok(calories) => sum += calories,
-            Err(_) => {
-                sums.push(sum);
-                sum = 0;
-            }
-        }
+use std::{cmp::Reverse, io::BufRead};
+
+use serde::Deserialize;
+
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+use crate::trace;
+
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// Number of elves carrying the most calories that are summed up.
+    pub top_count: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { top_count: 3 }
     }
+}
 
-    sums.push(sum);
+pub fn run(path: &str) -> Result<i32, Error> {
+    solve(input::open(path)?, &Params::default())
+}
+
+pub fn solve<R: BufRead>(reader: R, params: &Params) -> Result<i32, Error> {
+    let elves = parse_elves(reader)?;
+    timing::enter(Phase::Solve);
+
+    let mut sums: Vec<(usize, i32)> = elves.into_iter().enumerate().collect();
+    sums.sort_by_key(|x| Reverse(x.1));
+    for (index, calories) in sums.iter().take(params.top_count) {
+        trace::step(|| format!("elf {} carries {} calories", index + 1, calories));
+    }
+    sums.iter()
+        .take(params.top_count)
+        .try_fold(0i32, |sum, x| sum.checked_add(x.1))
+        .ok_or_else(|| {
+            Error::NoSolution(format!(
+                "total calories of top {} elves overflow",
+                params.top_count
+            ))
+        })
+}
 
-    sums.sort_by(|a, b| b.cmp(a));
-    sums[0..3].iter().sum()
+/// Total calories carried by every elf.
+fn parse_elves<R: BufRead>(reader: R) -> Result<Vec<i32>, Error> {
+    input::read_sections(reader)?
+        .iter()
+        .map(|x| {
+            x.parse_lines(|line| {
+                line.parse::<i32>()
+                    .map_err(|_| Error::parse(format!("invalid calories '{}'", line)))
+            })?
+            .into_iter()
+            .try_fold(0i32, |sum, x| sum.checked_add(x))
+            .ok_or_else(|| Error::parse("total calories of elf overflow").at_line(x.start))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn returns_error_on_invalid_calories() {
+        assert_eq!(
+            Err(Error::parse("invalid calories 'abc'").at_line(3)),
+            solve("1000\n\nabc\n".as_bytes(), &Params::default())
+        );
+    }
+
+    #[test]
+    fn returns_error_on_overflow() {
+        assert_eq!(
+            Err(Error::NoSolution(
+                "total calories of top 3 elves overflow".to_string()
+            )),
+            solve("2147483647\n\n1\n".as_bytes(), &Params::default())
+        );
+    }
+
+    #[test]
+    fn sums_configured_number_of_elves() {
+        let input = "1\n\n2\n\n3\n";
+
+        assert_eq!(Ok(5), solve(input.as_bytes(), &Params { top_count: 2 }));
+        assert_eq!(Ok(6), solve(input.as_bytes(), &Params { top_count: 5 }));
+    }
+
     #[test]
     fn returns_answer() {
-        let result = run("inputs/day1.txt");
-        println!("{}", result);
+        assert_eq!(Ok(45000), run("examples/day1.txt"));
     }
 }
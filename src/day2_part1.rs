@@ -1,7 +1,9 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
+use std::io::BufRead;
+
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+use crate::trace;
 
 #[derive(PartialEq, Debug)]
 enum RPSShape {
@@ -11,14 +13,14 @@ enum RPSShape {
 }
 
 impl TryFrom<&str> for RPSShape {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "A" | "X" => Ok(Self::Rock),
             "B" | "Y" => Ok(Self::Paper),
             "C" | "Z" => Ok(Self::Scissors),
-            _ => Err(format!("Unsupported value: {}", value)),
+            _ => Err(Error::parse(format!("Unsupported value: {}", value))),
         }
     }
 }
@@ -64,25 +66,43 @@ impl RPSResult {
     }
 }
 
-pub fn run(path: &str) -> i32 {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
+pub fn run(path: &str) -> Result<i32, Error> {
+    solve(input::open(path)?)
+}
 
-    let mut sum = 0;
-    for line_result in reader.lines() {
-        let line = line_result.unwrap();
-        sum += line_score(line);
-    }
+pub fn solve<R: BufRead>(reader: R) -> Result<i32, Error> {
+    let scores = input::parse_lines(reader, line_score)?;
+    timing::enter(Phase::Solve);
 
-    sum
+    scores
+        .iter()
+        .try_fold(0i32, |sum, &x| sum.checked_add(x))
+        .ok_or_else(|| Error::NoSolution("total score overflows".to_string()))
 }
 
-fn line_score(line: String) -> i32 {
-    let opponent_pick = RPSShape::try_from(&line[0..1]).unwrap();
-    let my_pick = RPSShape::try_from(&line[2..3]).unwrap();
+fn line_score(line: &str) -> Result<i32, Error> {
+    let (opponent, mine) = line.split_once(' ').ok_or_else(|| {
+        Error::parse(format!(
+            "expected exactly 2 space separated parts in line '{}'",
+            line
+        ))
+    })?;
+    let opponent_pick = RPSShape::try_from(opponent).map_err(|e| e.at_column(1))?;
+    let my_pick =
+        RPSShape::try_from(mine).map_err(|e| e.at_column(opponent.chars().count() + 2))?;
 
     let fight_result = my_pick.fight(&opponent_pick);
-    my_pick.score() + fight_result.score()
+    trace::step(|| {
+        format!(
+            "{:?} against {:?} is a {:?}, score {} + {}",
+            my_pick,
+            opponent_pick,
+            fight_result,
+            my_pick.score(),
+            fight_result.score()
+        )
+    });
+    Ok(my_pick.score() + fight_result.score())
 }
 
 #[cfg(test)]
@@ -101,7 +121,7 @@ mod tests {
         assert_eq!(Ok(RPSShape::Scissors), RPSShape::try_from("Z"));
 
         assert_eq!(
-            Err(String::from("Unsupported value: invalid")),
+            Err(Error::parse("Unsupported value: invalid")),
             RPSShape::try_from("invalid")
         );
     }
@@ -126,14 +146,35 @@ mod tests {
 
     #[test]
     fn returns_proper_score() {
-        assert_eq!(3, line_score(String::from("A Z"))); // rock vs scissors => 3 (scissors) + 0 (loss)
-        assert_eq!(8, line_score(String::from("A Y"))); // rock vs paper => 2 (paper) + 6 (win)
-        assert_eq!(4, line_score(String::from("A X"))); // rock vs rock => 1 (rock) + 3 (draw)
+        assert_eq!(Ok(3), line_score("A Z")); // rock vs scissors => 3 (scissors) + 0 (loss)
+        assert_eq!(Ok(8), line_score("A Y")); // rock vs paper => 2 (paper) + 6 (win)
+        assert_eq!(Ok(4), line_score("A X")); // rock vs rock => 1 (rock) + 3 (draw)
+    }
+
+    #[test]
+    fn returns_error_with_position() {
+        assert_eq!(
+            Err(Error::parse("Unsupported value: D").at_column(1)),
+            line_score("D X")
+        );
+        assert_eq!(
+            Err(Error::parse("Unsupported value: W").at_column(3)),
+            line_score("A W")
+        );
+        assert_eq!(
+            Err(Error::parse("Unsupported value: AB").at_column(1)),
+            line_score("AB X")
+        );
+        assert_eq!(
+            Err(Error::parse(
+                "expected exactly 2 space separated parts in line 'A'"
+            )),
+            line_score("A")
+        );
     }
 
     #[test]
     fn returns_answer() {
-        let result = run("inputs/day2.txt");
-        println!("{}", result);
+        assert_eq!(Ok(15), run("examples/day2.txt"));
     }
 }
@@ -1,7 +1,9 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
+use std::io::BufRead;
+
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+use crate::trace;
 
 #[derive(PartialEq, Debug)]
 struct Rucksack {
@@ -10,13 +12,20 @@ struct Rucksack {
 }
 
 impl Rucksack {
-    fn new(items_string: &str) -> Rucksack {
-        let half_length = items_string.len() / 2;
-
-        Rucksack {
-            first_compartment: Compartment::new(&items_string[..half_length]),
-            second_compartment: Compartment::new(&items_string[half_length..]),
-        }
+    fn new(items_string: &str) -> Result<Rucksack, Error> {
+        let mut items = items_string
+            .chars()
+            .enumerate()
+            .map(|(i, x)| Item::new(x).map_err(|e| e.at_column(i + 1)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let second_items = items.split_off(items.len() / 2);
+
+        Ok(Rucksack {
+            first_compartment: Compartment { items },
+            second_compartment: Compartment {
+                items: second_items,
+            },
+        })
     }
 
     fn find_duplicate_item(self) -> Option<Item> {
@@ -37,41 +46,96 @@ struct Compartment {
     items: Vec<Item>,
 }
 
-impl Compartment {
-    fn new(items_string: &str) -> Compartment {
-        let items: Vec<_> = items_string.chars().map(Item).collect();
-
-        Compartment { items }
-    }
-}
-
 #[derive(PartialEq, Debug)]
 struct Item(char);
 
 impl Item {
+    fn new(value: char) -> Result<Item, Error> {
+        if value.is_ascii_alphabetic() {
+            Ok(Item(value))
+        } else {
+            Err(Error::parse(format!("Unsupported character: {}", value)))
+        }
+    }
+
     fn priority(self) -> i32 {
         match self.0 {
             c @ 'a'..='z' => c as i32 - 'a' as i32 + 1,
-            c @ 'A'..='Z' => c as i32 - 'A' as i32 + 27,
-            c => panic!("Unsupported character: {}", c),
+            // Items are validated on creation, so anything else is uppercase
+            c => c as i32 - 'A' as i32 + 27,
         }
     }
 }
 
-pub fn run(path: &str) -> i32 {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
+pub fn run(path: &str) -> Result<i32, Error> {
+    solve(input::open(path)?)
+}
 
-    let mut sum = 0;
-    for line_result in reader.lines() {
-        let line = line_result.unwrap();
+pub fn solve<R: BufRead>(reader: R) -> Result<i32, Error> {
+    let rucksacks = input::parse_lines(reader, Rucksack::new)?;
+    timing::enter(Phase::Solve);
+
+    let mut sum: i32 = 0;
+    for (index, rucksack) in rucksacks.into_iter().enumerate() {
+        let duplicate = rucksack
+            .find_duplicate_item()
+            .ok_or_else(|| Error::parse("no item found in both compartments").at_line(index + 1))?;
+        let item = duplicate.0;
+        let priority = duplicate.priority();
+        trace::step(|| {
+            format!(
+                "rucksack {} has '{}' in both compartments, priority {}",
+                index + 1,
+                item,
+                priority
+            )
+        });
+        sum = sum
+            .checked_add(priority)
+            .ok_or_else(|| Error::NoSolution("sum of priorities overflows".to_string()))?;
+    }
 
-        let rucksack = Rucksack::new(&line);
-        let duplicate = rucksack.find_duplicate_item();
-        sum += duplicate.unwrap().priority();
+    Ok(sum)
+}
+
+/// Same as `solve`, but intersects the compartments as bitsets of item priorities instead of searching them.
+pub fn solve_bitset<R: BufRead>(reader: R) -> Result<i32, Error> {
+    let duplicates = input::parse_lines(reader, |x| {
+        item_bitset(x).map(|(first, second)| first & second)
+    })?;
+    timing::enter(Phase::Solve);
+
+    let mut sum: i32 = 0;
+    for (index, duplicate) in duplicates.into_iter().enumerate() {
+        if duplicate == 0 {
+            return Err(Error::parse("no item found in both compartments").at_line(index + 1));
+        }
+        sum = sum
+            .checked_add(duplicate.trailing_zeros() as i32)
+            .ok_or_else(|| Error::NoSolution("sum of priorities overflows".to_string()))?;
     }
 
-    sum
+    Ok(sum)
+}
+
+/// Items of a rucksack as a bitset, with the bit of every item's priority set.
+fn item_bitset(items_string: &str) -> Result<(u64, u64), Error> {
+    let priorities = items_string
+        .chars()
+        .enumerate()
+        .map(|(i, x)| {
+            Item::new(x)
+                .map(Item::priority)
+                .map_err(|e| e.at_column(i + 1))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (first, second) = priorities.split_at(priorities.len() / 2);
+
+    Ok((bitset(first), bitset(second)))
+}
+
+fn bitset(priorities: &[i32]) -> u64 {
+    priorities.iter().fold(0, |mask, x| mask | 1 << x)
 }
 
 #[cfg(test)]
@@ -89,18 +153,32 @@ mod tests {
             },
         };
 
-        assert_eq!(expected_rucksack, Rucksack::new("abcd"));
+        assert_eq!(Ok(expected_rucksack), Rucksack::new("abcd"));
     }
 
     #[test]
     fn returns_duplicate_item_if_present() {
-        let duplicate_rucksack = Rucksack::new("vJrwpWtwJgWrhcsFMMfFFhFp");
-        let non_duplicate_rucksack = Rucksack::new("vJrwWtwJgWrhcsFMMfFFhFp");
+        let duplicate_rucksack = Rucksack::new("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+        let non_duplicate_rucksack = Rucksack::new("vJrwWtwJgWrhcsFMMfFFhFp").unwrap();
 
         assert_eq!(Some(Item('p')), duplicate_rucksack.find_duplicate_item());
         assert_eq!(None, non_duplicate_rucksack.find_duplicate_item());
     }
 
+    #[test]
+    fn returns_error_on_unsupported_item() {
+        assert_eq!(
+            Err(Error::parse("Unsupported character: é").at_column(3)),
+            Rucksack::new("abéd")
+        );
+        assert_eq!(
+            Err(Error::parse("Unsupported character: 1")
+                .at_line(2)
+                .at_column(1)),
+            solve("ab\n1a\n".as_bytes())
+        );
+    }
+
     #[test]
     fn returns_item_priority() {
         assert_eq!(16, Item('p').priority());
@@ -113,7 +191,14 @@ mod tests {
 
     #[test]
     fn returns_answer() {
-        let result = run("inputs/day3.txt");
-        println!("{}", result);
+        assert_eq!(Ok(157), run("examples/day3.txt"));
+    }
+
+    #[test]
+    fn returns_answer_with_bitset() {
+        assert_eq!(
+            Ok(157),
+            solve_bitset(input::open("examples/day3.txt").unwrap())
+        );
     }
 }
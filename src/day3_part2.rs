@@ -1,7 +1,9 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
+use std::io::BufRead;
+
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+use crate::trace;
 
 #[derive(PartialEq, Debug)]
 struct Group {
@@ -11,12 +13,14 @@ struct Group {
 }
 
 impl Group {
-    fn new(bundle: [String; 3]) -> Group {
-        Group {
-            first_rucksack: Rucksack::new(&bundle[0]),
-            second_rucksack: Rucksack::new(&bundle[1]),
-            third_rucksack: Rucksack::new(&bundle[2]),
-        }
+    fn new(bundle: [String; 3]) -> Result<Group, Error> {
+        let rucksack = |i: usize| Rucksack::new(&bundle[i]).map_err(|e| e.at_line(i + 1));
+
+        Ok(Group {
+            first_rucksack: rucksack(0)?,
+            second_rucksack: rucksack(1)?,
+            third_rucksack: rucksack(2)?,
+        })
     }
 
     fn find_badge(self) -> Option<Item> {
@@ -43,13 +47,20 @@ struct Rucksack {
 }
 
 impl Rucksack {
-    fn new(items_string: &str) -> Rucksack {
-        let half_length = items_string.len() / 2;
+    fn new(items_string: &str) -> Result<Rucksack, Error> {
+        let mut items = items_string
+            .chars()
+            .enumerate()
+            .map(|(i, x)| Item::new(x).map_err(|e| e.at_column(i + 1)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let second_items = items.split_off(items.len() / 2);
 
-        Rucksack {
-            first_compartment: Compartment::new(&items_string[..half_length]),
-            second_compartment: Compartment::new(&items_string[half_length..]),
-        }
+        Ok(Rucksack {
+            first_compartment: Compartment { items },
+            second_compartment: Compartment {
+                items: second_items,
+            },
+        })
     }
 
     fn all_items(self) -> Vec<Item> {
@@ -66,51 +77,119 @@ struct Compartment {
     items: Vec<Item>,
 }
 
-impl Compartment {
-    fn new(items_string: &str) -> Compartment {
-        let items: Vec<_> = items_string.chars().map(Item).collect();
-
-        Compartment { items }
-    }
-}
-
 #[derive(PartialEq, Debug)]
 struct Item(char);
 
 impl Item {
+    fn new(value: char) -> Result<Item, Error> {
+        if value.is_ascii_alphabetic() {
+            Ok(Item(value))
+        } else {
+            Err(Error::parse(format!("Unsupported character: {}", value)))
+        }
+    }
+
     fn priority(self) -> i32 {
         match self.0 {
             c @ 'a'..='z' => c as i32 - 'a' as i32 + 1,
-            c @ 'A'..='Z' => c as i32 - 'A' as i32 + 27,
-            c => panic!("Unsupported character: {}", c),
+            // Items are validated on creation, so anything else is uppercase
+            c => c as i32 - 'A' as i32 + 27,
         }
     }
 }
 
-pub fn run(path: &str) -> i32 {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
+pub fn run(path: &str) -> Result<i32, Error> {
+    solve(input::open(path)?)
+}
+
+pub fn solve<R: BufRead>(reader: R) -> Result<i32, Error> {
+    let lines = input::read_lines(reader)?;
+    let mut groups = Vec::new();
+    for (index, chunk) in lines.chunks(3).enumerate() {
+        let group = match chunk {
+            [first, second, third] => Group::new([first.clone(), second.clone(), third.clone()])
+                .map_err(|e| e.offset_line(index * 3))?,
+            _ => {
+                return Err(Error::parse(format!(
+                    "incomplete group, expected 3 rucksacks, found {}",
+                    chunk.len()
+                ))
+                .at_line(index * 3 + 1))
+            }
+        };
+        groups.push(group);
+    }
+    timing::enter(Phase::Solve);
+
+    let mut sum: i32 = 0;
+    for (index, group) in groups.into_iter().enumerate() {
+        let badge = group
+            .find_badge()
+            .ok_or_else(|| Error::parse("no badge found in group").at_line(index * 3 + 1))?;
+        let item = badge.0;
+        let priority = badge.priority();
+        trace::step(|| {
+            format!(
+                "group {} has badge '{}', priority {}",
+                index + 1,
+                item,
+                priority
+            )
+        });
+        sum = sum
+            .checked_add(priority)
+            .ok_or_else(|| Error::NoSolution("sum of priorities overflows".to_string()))?;
+    }
 
-    let mut sum = 0;
+    Ok(sum)
+}
 
-    let mut line_iter = reader.lines();
-    'outer: loop {
-        let mut bundle: [String; 3] = Default::default();
+/// Same as `solve`, but intersects the rucksacks of a group as bitsets of item priorities instead of searching them.
+pub fn solve_bitset<R: BufRead>(reader: R) -> Result<i32, Error> {
+    let rucksacks = input::parse_lines(reader, |x| {
+        item_bitset(x).map(|(first, second)| first | second)
+    })?;
+    timing::enter(Phase::Solve);
 
-        for item in bundle.iter_mut() {
-            if let Some(result) = line_iter.next() {
-                item.insert_str(0, &result.unwrap());
-            } else {
-                break 'outer;
-            }
+    let mut sum: i32 = 0;
+    for (index, group) in rucksacks.chunks(3).enumerate() {
+        let [first, second, third] = group else {
+            return Err(Error::parse(format!(
+                "incomplete group, expected 3 rucksacks, found {}",
+                group.len()
+            ))
+            .at_line(index * 3 + 1));
+        };
+        let badge = first & second & third;
+        if badge == 0 {
+            return Err(Error::parse("no badge found in group").at_line(index * 3 + 1));
         }
-
-        let group = Group::new(bundle);
-        let badge = group.find_badge();
-        sum += badge.unwrap().priority();
+        sum = sum
+            .checked_add(badge.trailing_zeros() as i32)
+            .ok_or_else(|| Error::NoSolution("sum of priorities overflows".to_string()))?;
     }
 
-    sum
+    Ok(sum)
+}
+
+/// Items of a rucksack as a bitset, with the bit of every item's priority set.
+fn item_bitset(items_string: &str) -> Result<(u64, u64), Error> {
+    let priorities = items_string
+        .chars()
+        .enumerate()
+        .map(|(i, x)| {
+            Item::new(x)
+                .map(Item::priority)
+                .map_err(|e| e.at_column(i + 1))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (first, second) = priorities.split_at(priorities.len() / 2);
+
+    Ok((bitset(first), bitset(second)))
+}
+
+fn bitset(priorities: &[i32]) -> u64 {
+    priorities.iter().fold(0, |mask, x| mask | 1 << x)
 }
 
 #[cfg(test)]
@@ -147,7 +226,7 @@ mod tests {
         };
 
         let bundle: [String; 3] = [String::from("abcd"), String::from("ef"), String::from("gh")];
-        assert_eq!(expected_group, Group::new(bundle));
+        assert_eq!(Ok(expected_group), Group::new(bundle));
     }
 
     #[test]
@@ -163,8 +242,25 @@ mod tests {
             String::from("PmmdzqPrVvPwwTWBwg"),
         ];
 
-        assert_eq!(Some(Item('r')), Group::new(duplicate_bundle).find_badge());
-        assert_eq!(None, Group::new(non_duplicate_bundle).find_badge());
+        assert_eq!(
+            Some(Item('r')),
+            Group::new(duplicate_bundle).unwrap().find_badge()
+        );
+        assert_eq!(None, Group::new(non_duplicate_bundle).unwrap().find_badge());
+    }
+
+    #[test]
+    fn returns_error_with_position() {
+        assert_eq!(
+            Err(Error::parse("Unsupported character: 1")
+                .at_line(5)
+                .at_column(2)),
+            solve("ab\ncd\nef\ngh\na1\nij\n".as_bytes())
+        );
+        assert_eq!(
+            Err(Error::parse("incomplete group, expected 3 rucksacks, found 2").at_line(4)),
+            solve("ab\ncd\nef\ngh\nij\n".as_bytes())
+        );
     }
 
     #[test]
@@ -179,7 +275,14 @@ mod tests {
 
     #[test]
     fn returns_answer() {
-        let result = run("inputs/day3.txt");
-        println!("{}", result);
+        assert_eq!(Ok(70), run("examples/day3.txt"));
+    }
+
+    #[test]
+    fn returns_answer_with_bitset() {
+        assert_eq!(
+            Ok(70),
+            solve_bitset(input::open("examples/day3.txt").unwrap())
+        );
     }
 }
@@ -1,7 +1,9 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
+use std::{fmt, io::BufRead};
+
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+use crate::trace;
 
 #[derive(PartialEq, Debug)]
 struct Pair {
@@ -10,21 +12,34 @@ struct Pair {
 }
 
 impl Pair {
-    fn new(pair_string: &str) -> Pair {
+    fn new(pair_string: &str) -> Result<Pair, Error> {
         let (first_assignment_string, second_assignment_string) =
-            pair_string.split_once(',').unwrap();
-
-        Pair {
-            first_assignment: Assignment::new(first_assignment_string),
-            second_assignment: Assignment::new(second_assignment_string),
-        }
+            pair_string.split_once(',').ok_or_else(|| {
+                Error::parse(format!(
+                    "expected 2 comma separated assignments in '{}'",
+                    pair_string
+                ))
+            })?;
+
+        Ok(Pair {
+            first_assignment: Assignment::new(first_assignment_string)
+                .map_err(|e| e.at_column(1))?,
+            second_assignment: Assignment::new(second_assignment_string)
+                .map_err(|e| e.at_column(first_assignment_string.chars().count() + 2))?,
+        })
     }
 
-    fn has_overlapped_assignment(self) -> bool {
+    fn has_overlapped_assignment(&self) -> bool {
         self.first_assignment.overlaps(&self.second_assignment)
     }
 }
 
+impl fmt::Display for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.first_assignment, self.second_assignment)
+    }
+}
+
 #[derive(PartialEq, Debug)]
 struct Assignment {
     lower_bound: i32,
@@ -32,13 +47,25 @@ struct Assignment {
 }
 
 impl Assignment {
-    fn new(assignment_string: &str) -> Assignment {
-        let (lower_bound_string, upper_bound_string) = assignment_string.split_once('-').unwrap();
+    fn new(assignment_string: &str) -> Result<Assignment, Error> {
+        let (lower_bound_string, upper_bound_string) =
+            assignment_string.split_once('-').ok_or_else(|| {
+                Error::parse(format!(
+                    "expected range in assignment '{}'",
+                    assignment_string
+                ))
+            })?;
+
+        Ok(Assignment {
+            lower_bound: Self::parse_bound(lower_bound_string)?,
+            upper_bound: Self::parse_bound(upper_bound_string)?,
+        })
+    }
 
-        Assignment {
-            lower_bound: lower_bound_string.parse().unwrap(),
-            upper_bound: upper_bound_string.parse().unwrap(),
-        }
+    fn parse_bound(bound_string: &str) -> Result<i32, Error> {
+        bound_string
+            .parse()
+            .map_err(|_| Error::parse(format!("invalid bound '{}'", bound_string)))
     }
 
     fn overlaps(&self, other: &Assignment) -> bool {
@@ -46,21 +73,29 @@ impl Assignment {
     }
 }
 
-pub fn run(path: &str) -> i32 {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.lower_bound, self.upper_bound)
+    }
+}
 
-    let mut count = 0;
-    for line_result in reader.lines() {
-        let line = line_result.unwrap();
+pub fn run(path: &str) -> Result<i32, Error> {
+    solve(input::open(path)?)
+}
+
+pub fn solve<R: BufRead>(reader: R) -> Result<i32, Error> {
+    let pairs = input::parse_lines(reader, Pair::new)?;
+    timing::enter(Phase::Solve);
 
-        let pair = Pair::new(&line);
+    let mut count = 0;
+    for (index, pair) in pairs.into_iter().enumerate() {
         if pair.has_overlapped_assignment() {
+            trace::step(|| format!("pair {} ({}): assignments overlap", index + 1, pair));
             count += 1;
         }
     }
 
-    count
+    Ok(count)
 }
 
 #[cfg(test)]
@@ -79,22 +114,39 @@ mod tests {
                 upper_bound: 8,
             },
         };
-        assert_eq!(expected_pair, Pair::new("2-4,6-8"));
+        assert_eq!(Ok(expected_pair), Pair::new("2-4,6-8"));
     }
 
     #[test]
     fn checks_if_has_overlapped_assignment() {
-        assert!(Pair::new("1-3,2-2").has_overlapped_assignment());
-        assert!(Pair::new("2-2,1-3").has_overlapped_assignment());
-        assert!(Pair::new("1-3,1-3").has_overlapped_assignment());
-        assert!(Pair::new("1-3,2-4").has_overlapped_assignment());
+        assert!(Pair::new("1-3,2-2").unwrap().has_overlapped_assignment());
+        assert!(Pair::new("2-2,1-3").unwrap().has_overlapped_assignment());
+        assert!(Pair::new("1-3,1-3").unwrap().has_overlapped_assignment());
+        assert!(Pair::new("1-3,2-4").unwrap().has_overlapped_assignment());
+
+        assert!(!Pair::new("1-2,4-5").unwrap().has_overlapped_assignment());
+    }
 
-        assert!(!Pair::new("1-2,4-5").has_overlapped_assignment());
+    #[test]
+    fn returns_error_with_position() {
+        assert_eq!(
+            Err(Error::parse(
+                "expected 2 comma separated assignments in '2-4'"
+            )),
+            Pair::new("2-4")
+        );
+        assert_eq!(
+            Err(Error::parse("expected range in assignment '24'").at_column(1)),
+            Pair::new("24,6-8")
+        );
+        assert_eq!(
+            Err(Error::parse("invalid bound 'x'").at_column(5)),
+            Pair::new("2-4,x-8")
+        );
     }
 
     #[test]
     fn returns_answer() {
-        let result = run("inputs/day4.txt");
-        println!("{}", result);
+        assert_eq!(Ok(4), run("examples/day4.txt"));
     }
 }
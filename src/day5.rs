@@ -1,55 +1,118 @@
-mod commands;
-mod crate_stacks;
-mod lib;
+pub mod commands;
+pub mod crate_stacks;
+pub mod lib;
 
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
+use std::io::BufRead;
 
 use self::{commands::Commands, crate_stacks::CrateStacks};
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
 
-pub fn run_part1(path: &str) -> String {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
+pub fn run_part1(path: &str) -> Result<String, Error> {
+    solve_part1(input::open(path)?)
+}
 
-    let line_iter = reader.lines();
-    let (stack_lines, command_lines) = lib::split_lines(line_iter);
+pub fn run_part2(path: &str) -> Result<String, Error> {
+    solve_part2(input::open(path)?)
+}
 
-    let crate_stacks = CrateStacks::new(&stack_lines).unwrap();
-    let commands = Commands::new(&command_lines).unwrap();
-    let updated_stacks = crate_stacks.update(commands, lib::Order::Lifo).unwrap();
+pub fn solve_part1<R: BufRead>(reader: R) -> Result<String, Error> {
+    solve(reader, lib::Order::Lifo)
+}
+
+pub fn solve_part2<R: BufRead>(reader: R) -> Result<String, Error> {
+    solve(reader, lib::Order::Fifo)
+}
 
-    updated_stacks.tops_string()
+/// Drawing of the stacks before the rearrangement, or after it if a `pop_order` is given.
+pub fn draw<R: BufRead>(reader: R, pop_order: Option<lib::Order>) -> Result<String, Error> {
+    rearrange(reader, pop_order, |x| x.to_string())
 }
 
-pub fn run_part2(path: &str) -> String {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
+fn solve<R: BufRead>(reader: R, pop_order: lib::Order) -> Result<String, Error> {
+    rearrange(reader, Some(pop_order), |x| x.tops_string())
+}
 
-    let line_iter = reader.lines();
-    let (stack_lines, command_lines) = lib::split_lines(line_iter);
+fn rearrange<R, T, F>(reader: R, pop_order: Option<lib::Order>, f: F) -> Result<T, Error>
+where
+    R: BufRead,
+    F: FnOnce(&CrateStacks) -> T,
+{
+    let sections = input::read_sections(reader)?;
+    let (stack_section, command_section) = match sections.as_slice() {
+        [stacks] => (stacks, None),
+        [stacks, commands] => (stacks, Some(commands)),
+        [] => return Err(Error::parse("expected crate stacks")),
+        [.., unexpected] => {
+            return Err(Error::parse("unexpected section after commands").at_line(unexpected.start))
+        }
+    };
 
-    let crate_stacks = CrateStacks::new(&stack_lines).unwrap();
-    let commands = Commands::new(&command_lines).unwrap();
-    let updated_stacks = crate_stacks.update(commands, lib::Order::Fifo).unwrap();
+    let crate_stacks = CrateStacks::new(&stack_section.lines)?;
+    let commands = match command_section {
+        Some(section) => {
+            Commands::new(&section.lines).map_err(|e| e.offset_line(section.start - 1))?
+        }
+        None => Commands::new(&[])?,
+    };
+    timing::enter(Phase::Solve);
 
-    updated_stacks.tops_string()
+    match pop_order {
+        Some(order) => Ok(f(&crate_stacks.update(commands, order)?)),
+        None => Ok(f(&crate_stacks)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn returns_error_with_line_number_of_invalid_command() {
+        let input = "[A]\n 1 \n\nmove 1 from 1 to 1\nmove x from 1 to 1\n";
+
+        assert_eq!(
+            Err(
+                Error::parse("invalid count 'x' in command 'move x from 1 to 1'")
+                    .at_line(5)
+                    .at_column(6)
+            ),
+            solve_part1(input.as_bytes())
+        );
+    }
+
+    #[test]
+    fn returns_error_on_extra_section() {
+        let input = "[A]\n 1 \n\nmove 1 from 1 to 1\n\nmove 1 from 1 to 1\n";
+
+        assert_eq!(
+            Err(Error::parse("unexpected section after commands").at_line(6)),
+            solve_part1(input.as_bytes())
+        );
+    }
+
+    #[test]
+    fn draws_stacks_before_and_after_rearrangement() {
+        let input = "[A]\n[B] [C]\n 1   2\n\nmove 1 from 1 to 2\n";
+
+        assert_eq!(
+            Ok("[A]\n[B] [C]\n 1   2".to_string()),
+            draw(input.as_bytes(), None)
+        );
+        assert_eq!(
+            Ok("    [A]\n[B] [C]\n 1   2".to_string()),
+            draw(input.as_bytes(), Some(lib::Order::Lifo))
+        );
+    }
+
     #[test]
     fn returns_answer_for_part1() {
-        let result = run_part1("inputs/day5.txt");
-        println!("{}", result);
+        assert_eq!(Ok("CMZ".to_string()), run_part1("examples/day5.txt"));
     }
 
     #[test]
     fn returns_answer_for_part2() {
-        let result = run_part2("inputs/day5.txt");
-        println!("{}", result);
+        assert_eq!(Ok("MCD".to_string()), run_part2("examples/day5.txt"));
     }
 }
@@ -3,19 +3,25 @@ use std::vec::IntoIter;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::error::Error;
+
 #[derive(PartialEq, Debug)]
 pub struct Commands<'a> {
     storage: Vec<Command<'a>>,
 }
 
 impl<'a> Commands<'a> {
-    pub fn new(command_lines: &'a Vec<String>) -> Result<Commands<'a>, String> {
+    pub fn new(command_lines: &'a [String]) -> Result<Commands<'a>, Error> {
         let mut result = Commands {
             storage: Vec::with_capacity(command_lines.len()),
         };
 
-        for line in command_lines {
-            result.storage.push(line.as_str().try_into()?);
+        for (index, line) in command_lines.iter().enumerate() {
+            let command = line
+                .as_str()
+                .try_into()
+                .map_err(|e: Error| e.at_line(index + 1))?;
+            result.storage.push(command);
         }
 
         Ok(result)
@@ -47,31 +53,45 @@ lazy_static! {
 }
 
 impl<'a> TryFrom<&'a str> for Command<'a> {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         let captures = COMMAND_REGEX
             .captures(s)
-            .ok_or(format!("invalid command '{}'", s))?;
-        let count_string = captures.name("count").unwrap().as_str();
-        let count: i32 = count_string
-            .parse()
-            .map_err(|_| format!("invalid count '{}' in command '{}'", count_string, s))?;
+            .ok_or_else(|| Error::parse(format!("invalid command '{}'", s)))?;
+        let count_match = captures.name("count").unwrap();
+        let count_string = count_match.as_str();
+        let count: i32 = count_string.parse().map_err(|_| {
+            Error::parse(format!(
+                "invalid count '{}' in command '{}'",
+                count_string, s
+            ))
+            .at_column(count_match.start() + 1)
+        })?;
         if count <= 0 {
-            return Err(format!(
+            return Err(Error::parse(format!(
                 "nonpositive count '{}' in command '{}'",
                 count_string, s
-            ));
+            ))
+            .at_column(count_match.start() + 1));
         }
 
-        let from = captures.name("from").unwrap().as_str();
+        let from_match = captures.name("from").unwrap();
+        let from = from_match.as_str();
         if WHITESPACE_REGEX.is_match(from) {
-            return Err(format!("invalid from '{}' in command '{}'", from, s));
+            return Err(
+                Error::parse(format!("invalid from '{}' in command '{}'", from, s))
+                    .at_column(from_match.start() + 1),
+            );
         }
 
-        let to = captures.name("to").unwrap().as_str();
+        let to_match = captures.name("to").unwrap();
+        let to = to_match.as_str();
         if WHITESPACE_REGEX.is_match(to) {
-            return Err(format!("invalid to '{}' in command '{}'", to, s));
+            return Err(
+                Error::parse(format!("invalid to '{}' in command '{}'", to, s))
+                    .at_column(to_match.start() + 1),
+            );
         }
 
         Ok(Command {
@@ -84,7 +104,8 @@ impl<'a> TryFrom<&'a str> for Command<'a> {
 }
 
 impl<'a> Command<'a> {
-    pub fn to_string(&self) -> &'a str {
+    /// The command as written in the input.
+    pub fn as_str(&self) -> &'a str {
         self.original_str
     }
 }
@@ -133,7 +154,7 @@ mod tests {
             ];
 
             assert_eq!(
-                Err("invalid command 'invalid'".to_string()),
+                Err(Error::parse("invalid command 'invalid'").at_line(2)),
                 Commands::new(&command_lines)
             );
         }
@@ -145,11 +166,11 @@ mod tests {
         #[test]
         fn handles_syntax_errors() {
             assert_eq!(
-                Err::<Command, _>("invalid command 'invalid'".to_string()),
+                Err::<Command, _>(Error::parse("invalid command 'invalid'")),
                 "invalid".try_into()
             ); // completely invalid
             assert_eq!(
-                Err::<Command, _>("invalid command 'amove 3 from 8 to 9'".to_string()),
+                Err::<Command, _>(Error::parse("invalid command 'amove 3 from 8 to 9'")),
                 "amove 3 from 8 to 9".try_into()
             ); // leading chars forbidden
         }
@@ -157,11 +178,15 @@ mod tests {
         #[test]
         fn handles_count_errors() {
             assert_eq!(
-                Err::<Command, _>("invalid count ' ' in command 'move   from 8 to 9'".to_string()),
+                Err::<Command, _>(
+                    Error::parse("invalid count ' ' in command 'move   from 8 to 9'").at_column(6)
+                ),
                 "move   from 8 to 9".try_into()
             );
             assert_eq!(
-                Err::<Command, _>("invalid count 'a' in command 'move a from 8 to 9'".to_string()),
+                Err::<Command, _>(
+                    Error::parse("invalid count 'a' in command 'move a from 8 to 9'").at_column(6)
+                ),
                 "move a from 8 to 9".try_into()
             );
         }
@@ -170,13 +195,15 @@ mod tests {
         fn handles_nonpositive_count_errors() {
             assert_eq!(
                 Err::<Command, _>(
-                    "nonpositive count '0' in command 'move 0 from 8 to 9'".to_string()
+                    Error::parse("nonpositive count '0' in command 'move 0 from 8 to 9'")
+                        .at_column(6)
                 ),
                 "move 0 from 8 to 9".try_into()
             );
             assert_eq!(
                 Err::<Command, _>(
-                    "nonpositive count '-1' in command 'move -1 from 8 to 9'".to_string()
+                    Error::parse("nonpositive count '-1' in command 'move -1 from 8 to 9'")
+                        .at_column(6)
                 ),
                 "move -1 from 8 to 9".try_into()
             );
@@ -185,11 +212,16 @@ mod tests {
         #[test]
         fn handles_from_identifier_errors() {
             assert_eq!(
-                Err::<Command, _>("invalid from ' ' in command 'move 3 from   to 9'".to_string()),
+                Err::<Command, _>(
+                    Error::parse("invalid from ' ' in command 'move 3 from   to 9'").at_column(13)
+                ),
                 "move 3 from   to 9".try_into()
             );
             assert_eq!(
-                Err::<Command, _>("invalid from '8 ' in command 'move 3 from 8  to 9'".to_string()),
+                Err::<Command, _>(
+                    Error::parse("invalid from '8 ' in command 'move 3 from 8  to 9'")
+                        .at_column(13)
+                ),
                 "move 3 from 8  to 9".try_into()
             );
         }
@@ -197,11 +229,15 @@ mod tests {
         #[test]
         fn handles_to_identifier_errors() {
             assert_eq!(
-                Err::<Command, _>("invalid to ' ' in command 'move 3 from 8 to  '".to_string()),
+                Err::<Command, _>(
+                    Error::parse("invalid to ' ' in command 'move 3 from 8 to  '").at_column(18)
+                ),
                 "move 3 from 8 to  ".try_into()
             );
             assert_eq!(
-                Err::<Command, _>("invalid to '9 ' in command 'move 3 from 8 to 9 '".to_string()),
+                Err::<Command, _>(
+                    Error::parse("invalid to '9 ' in command 'move 3 from 8 to 9 '").at_column(18)
+                ),
                 "move 3 from 8 to 9 ".try_into()
             );
         }
@@ -1,8 +1,14 @@
+use std::fmt;
+
 use indexmap::IndexMap;
+use itertools::Itertools;
 use regex::Regex;
+use tracing::{debug, trace_span};
 
 use super::commands::Commands;
 use super::lib::{Order, Stack};
+use crate::error::Error;
+use crate::trace;
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct CrateStacks<'a> {
@@ -10,8 +16,10 @@ pub struct CrateStacks<'a> {
 }
 
 impl<'a> CrateStacks<'a> {
-    pub fn new(stack_lines: &'a [String]) -> Result<CrateStacks<'a>, String> {
-        let (id_line, content_lines) = stack_lines.split_last().unwrap();
+    pub fn new(stack_lines: &'a [String]) -> Result<CrateStacks<'a>, Error> {
+        let (id_line, content_lines) = stack_lines
+            .split_last()
+            .ok_or_else(|| Error::parse("expected line of stack ids"))?;
         let id_regex = Regex::new(r"\S+").unwrap();
 
         let mut id_lookup = IndexMap::new();
@@ -19,10 +27,11 @@ impl<'a> CrateStacks<'a> {
             let id = r#match.as_str();
             match id_lookup.entry(id) {
                 indexmap::map::Entry::Vacant(_) => {
-                    id_lookup.insert(id, r#match.start());
+                    // Crates are looked up by char, the match starts at a byte offset
+                    id_lookup.insert(id, id_line[..r#match.start()].chars().count());
                 }
                 indexmap::map::Entry::Occupied(_) => {
-                    return Err(format!("duplicate stack id '{}'", id));
+                    return Err(Error::DuplicateStack(id.to_string()));
                 }
             }
         }
@@ -36,10 +45,11 @@ impl<'a> CrateStacks<'a> {
 
         for line in content_lines.iter().rev() {
             for (&id, &index) in id_lookup.iter() {
-                let crate_char = line.chars().nth(index).unwrap();
-                if crate_char == ' ' {
-                    continue;
-                }
+                // Trailing blanks of a line may be trimmed away
+                let crate_char = match line.chars().nth(index) {
+                    Some(' ') | None => continue,
+                    Some(x) => x,
+                };
 
                 let stack = result.storage.get_mut(id).unwrap();
                 stack.push(Crate(crate_char));
@@ -49,34 +59,47 @@ impl<'a> CrateStacks<'a> {
         Ok(result)
     }
 
-    pub fn update(&self, commands: Commands, pop_order: Order) -> Result<CrateStacks, String> {
+    pub fn update(&self, commands: Commands, pop_order: Order) -> Result<CrateStacks<'_>, Error> {
         let mut result = self.clone();
 
-        for command in commands {
-            let from_stack = result.storage.get_mut(command.from).ok_or(format!(
-                "unknown stack id '{}' specified in command '{}'",
-                command.from,
-                command.to_string()
-            ))?;
+        for (index, command) in commands.into_iter().enumerate() {
+            let _span =
+                trace_span!("command", index = index + 1, command = command.as_str()).entered();
+            let from_stack =
+                result
+                    .storage
+                    .get_mut(command.from)
+                    .ok_or_else(|| Error::UnknownStack {
+                        id: command.from.to_string(),
+                        command: command.as_str().to_owned(),
+                    })?;
             let from_stack_len = from_stack.len();
             let items: Vec<_> = match from_stack.pop_many_iter(command.count, pop_order) {
                 Ok(iter) => iter.collect(),
                 Err(_) => {
-                    return Err(format!(
-                        "not enough items ({}) in stack '{}' specified in command '{}'",
-                        from_stack_len,
-                        command.from,
-                        command.to_string()
-                    ));
+                    debug!(
+                        stack = command.from,
+                        available = from_stack_len,
+                        "not enough crates"
+                    );
+                    return Err(Error::NotEnoughCrates {
+                        stack: command.from.to_string(),
+                        available: from_stack_len,
+                        command: command.as_str().to_owned(),
+                    });
                 }
             };
 
-            let to_stack = result.storage.get_mut(command.to).ok_or(format!(
-                "unknown stack id '{}' specified in command '{}'",
-                command.to,
-                command.to_string()
-            ))?;
+            let to_stack =
+                result
+                    .storage
+                    .get_mut(command.to)
+                    .ok_or_else(|| Error::UnknownStack {
+                        id: command.to.to_string(),
+                        command: command.as_str().to_owned(),
+                    })?;
             to_stack.push_many(items.into_iter());
+            trace::step(|| format!("{}, tops {}", command.as_str(), result.tops_string()));
         }
 
         Ok(result)
@@ -85,12 +108,45 @@ impl<'a> CrateStacks<'a> {
     pub fn tops_string(&self) -> String {
         self.storage
             .iter()
-            .filter(|(_, c)| c.top().is_some())
-            .map(|(_, c)| c.top().unwrap().0)
+            .filter_map(|(_, c)| c.top())
+            .map(|x| x.0)
             .collect()
     }
 }
 
+/// Draws the stacks the way the puzzle does, every column as wide as its id and trailing blanks trimmed.
+impl fmt::Display for CrateStacks<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths: Vec<usize> = self
+            .storage
+            .keys()
+            .map(|x| x.chars().count().max(3))
+            .collect();
+        let height = self.storage.values().map(|x| x.len()).max().unwrap_or(0);
+
+        for level in (0..height).rev() {
+            let line = self
+                .storage
+                .values()
+                .zip(&widths)
+                .map(|(stack, &width)| match stack.get(level) {
+                    Some(Crate(x)) => format!("{:^width$}", format!("[{}]", x)),
+                    None => " ".repeat(width),
+                })
+                .join(" ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        let ids = self
+            .storage
+            .keys()
+            .zip(&widths)
+            .map(|(id, &width)| format!("{:^width$}", id))
+            .join(" ");
+        write!(f, "{}", ids.trim_end())
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct Crate(pub char);
 
@@ -148,7 +204,31 @@ mod tests {
             ];
 
             assert_eq!(
-                Err("duplicate stack id '1'".to_string()),
+                Err(Error::DuplicateStack("1".to_string())),
+                CrateStacks::new(&stack_lines)
+            );
+        }
+
+        #[test]
+        fn handles_missing_id_line() {
+            assert_eq!(
+                Err(Error::parse("expected line of stack ids")),
+                CrateStacks::new(&[])
+            );
+        }
+
+        #[test]
+        fn handles_multibyte_chars() {
+            let mut expected_stacks = IndexMap::<_, Stack<Crate>>::new();
+            expected_stacks.insert("ä", "Ü".into());
+            expected_stacks.insert("2", "F".into());
+
+            let stack_lines = vec!["[Ü] [F]".to_string(), " ä   2 ".to_string()];
+
+            assert_eq!(
+                Ok(CrateStacks {
+                    storage: expected_stacks
+                }),
                 CrateStacks::new(&stack_lines)
             );
         }
@@ -200,7 +280,10 @@ mod tests {
             let commands = Commands::new(&command_lines).unwrap();
 
             assert_eq!(
-                Err("unknown stack id '3' specified in command 'move 3 from 3 to 1'".to_string()),
+                Err(Error::UnknownStack {
+                    id: "3".to_string(),
+                    command: "move 3 from 3 to 1".to_string()
+                }),
                 stacks.update(commands, Order::Lifo)
             )
         }
@@ -219,7 +302,10 @@ mod tests {
             let commands = Commands::new(&command_lines).unwrap();
 
             assert_eq!(
-                Err("unknown stack id '3' specified in command 'move 3 from 1 to 3'".to_string()),
+                Err(Error::UnknownStack {
+                    id: "3".to_string(),
+                    command: "move 3 from 1 to 3".to_string()
+                }),
                 stacks.update(commands, Order::Lifo)
             )
         }
@@ -238,10 +324,11 @@ mod tests {
             let commands = Commands::new(&command_lines).unwrap();
 
             assert_eq!(
-                Err(
-                    "not enough items (3) in stack '1' specified in command 'move 4 from 1 to 2'"
-                        .to_string()
-                ),
+                Err(Error::NotEnoughCrates {
+                    stack: "1".to_string(),
+                    available: 3,
+                    command: "move 4 from 1 to 2".to_string()
+                }),
                 stacks.update(commands, Order::Lifo)
             )
         }
@@ -268,6 +355,35 @@ mod tests {
         }
     }
 
+    mod display {
+        use crate::day5::crate_stacks::*;
+
+        #[test]
+        fn draws_stacks_like_the_puzzle() {
+            let stack_lines = vec![
+                "    [D]    ".to_string(),
+                "[N] [C]    ".to_string(),
+                "[Z] [M] [P]".to_string(),
+                " 1   2   3 ".to_string(),
+            ];
+
+            assert_eq!(
+                "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3",
+                CrateStacks::new(&stack_lines).unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn widens_columns_of_long_ids() {
+            let stack_lines = vec!["A      B".to_string(), "multi  2".to_string()];
+
+            assert_eq!(
+                " [A]  [B]\nmulti  2",
+                CrateStacks::new(&stack_lines).unwrap().to_string()
+            );
+        }
+    }
+
     mod tops_string {
         use crate::day5::crate_stacks::*;
 
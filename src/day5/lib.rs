@@ -1,5 +1,3 @@
-use std::io;
-
 #[derive(PartialEq, Debug, Clone)]
 pub struct Stack<T> {
     storage: Vec<T>,
@@ -26,7 +24,11 @@ impl<T> Stack<T> {
         self.storage.last()
     }
 
-    #[allow(dead_code)]
+    /// Item at `index`, counting from the bottom.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.storage.get(index)
+    }
+
     pub fn pop(&mut self) -> Result<T, &'static str> {
         self.storage.pop().ok_or("empty stack")
     }
@@ -53,6 +55,16 @@ impl<T> Stack<T> {
     pub fn len(&self) -> usize {
         self.storage.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
 }
 
 impl<T> FromIterator<T> for Stack<T> {
@@ -70,26 +82,6 @@ pub enum Order {
     Fifo,
 }
 
-pub fn split_lines<T: Iterator<Item = io::Result<String>>>(
-    mut iter: T,
-) -> (Vec<String>, Vec<String>) {
-    let mut stack_lines = Vec::new();
-    while let Some(Ok(line)) = iter.next() {
-        if !line.is_empty() {
-            stack_lines.push(line);
-        } else {
-            break;
-        }
-    }
-
-    let mut command_lines = Vec::new();
-    while let Some(Ok(line)) = iter.next() {
-        command_lines.push(line);
-    }
-
-    (stack_lines, command_lines)
-}
-
 #[cfg(test)]
 mod tests {
     mod stack {
@@ -157,26 +149,4 @@ mod tests {
             );
         }
     }
-
-    mod split_lines {
-        use crate::day5::lib::*;
-
-        #[test]
-        fn splits_file_into_stack_and_command_lines() {
-            let file_lines = vec![
-                Ok("stack1".to_string()),
-                Ok("stack2".to_string()),
-                Ok("".to_string()),
-                Ok("command1".to_string()),
-                Ok("command2".to_string()),
-            ];
-
-            let (stack_lines, command_lines) = split_lines(file_lines.into_iter());
-            let expected_stack_lines = vec!["stack1".to_string(), "stack2".to_string()];
-            let expected_command_lines = vec!["command1".to_string(), "command2".to_string()];
-
-            assert_eq!(expected_stack_lines, stack_lines);
-            assert_eq!(expected_command_lines, command_lines);
-        }
-    }
 }
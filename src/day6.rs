@@ -1,28 +1,85 @@
-mod data_reader;
-mod lib;
+pub mod data_reader;
+pub mod lib;
 
-use std::{
-    fs::File,
-    io::{self, Read},
-};
+use std::io::BufRead;
+
+use serde::Deserialize;
 
 use self::data_reader::DataReader;
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// Number of distinct characters making up a start-of-packet marker.
+    pub packet_marker_size: usize,
+    /// Number of distinct characters making up a start-of-message marker.
+    pub message_marker_size: usize,
+}
 
-pub fn run(path: &str) -> (usize, usize) {
-    let file = File::open(path).unwrap();
-    let mut reader = io::BufReader::new(file);
-    let mut data = String::new();
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            packet_marker_size: data_reader::PACKET_MARKER_SIZE,
+            message_marker_size: data_reader::MESSAGE_MARKER_SIZE,
+        }
+    }
+}
+
+pub fn run_part1(path: &str) -> Result<usize, Error> {
+    solve_part1(input::open(path)?, &Params::default())
+}
+
+pub fn run_part2(path: &str) -> Result<usize, Error> {
+    solve_part2(input::open(path)?, &Params::default())
+}
 
-    reader.read_to_string(&mut data).unwrap();
-    let data_reader = DataReader::new(data);
+pub fn solve_part1<R: BufRead>(reader: R, params: &Params) -> Result<usize, Error> {
+    let data_reader = read_data(reader, params)?;
+    let start_of_packet = data_reader
+        .find_start_of_packet()
+        .ok_or_else(|| Error::NoSolution("start-of-packet marker not found".to_string()))?;
+
+    Ok(start_of_packet.get_chars_processed())
+}
+
+pub fn solve_part2<R: BufRead>(reader: R, params: &Params) -> Result<usize, Error> {
+    let data_reader = read_data(reader, params)?;
+    let start_of_message = data_reader
+        .find_start_of_message()
+        .ok_or_else(|| Error::NoSolution("start-of-message marker not found".to_string()))?;
+
+    Ok(start_of_message.get_chars_processed())
+}
+
+/// Same as `solve_part1` with the default marker sizes, but finds the marker in a single pass.
+pub fn solve_part1_linear<R: BufRead>(reader: R) -> Result<usize, Error> {
+    let data_reader = read_data(reader, &Params::default())?;
+    let start_of_packet = data_reader
+        .find_start_of_packet_linear()
+        .ok_or_else(|| Error::NoSolution("start-of-packet marker not found".to_string()))?;
+
+    Ok(start_of_packet.get_chars_processed())
+}
 
-    let start_of_packet = data_reader.find_start_of_packet().unwrap();
-    let start_of_message = data_reader.find_start_of_message().unwrap();
+/// Same as `solve_part2` with the default marker sizes, but finds the marker in a single pass.
+pub fn solve_part2_linear<R: BufRead>(reader: R) -> Result<usize, Error> {
+    let data_reader = read_data(reader, &Params::default())?;
+    let start_of_message = data_reader
+        .find_start_of_message_linear()
+        .ok_or_else(|| Error::NoSolution("start-of-message marker not found".to_string()))?;
 
-    (
-        start_of_packet.get_chars_processed(),
-        start_of_message.get_chars_processed(),
-    )
+    Ok(start_of_message.get_chars_processed())
+}
+
+fn read_data<R: BufRead>(reader: R, params: &Params) -> Result<DataReader, Error> {
+    let result = DataReader::new(input::read_lines(reader)?.concat())
+        .with_marker_sizes(params.packet_marker_size, params.message_marker_size);
+    timing::enter(Phase::Solve);
+
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -30,8 +87,43 @@ mod tests {
     use super::*;
 
     #[test]
-    fn returns_answer() {
-        let result = run("inputs/day6.txt");
-        println!("{:?}", result);
+    fn returns_error_if_marker_not_found() {
+        assert_eq!(
+            Err(Error::NoSolution(
+                "start-of-packet marker not found".to_string()
+            )),
+            solve_part1("abab".as_bytes(), &Params::default())
+        );
+    }
+
+    #[test]
+    fn ignores_line_ending() {
+        assert_eq!(
+            Err(Error::NoSolution(
+                "start-of-packet marker not found".to_string()
+            )),
+            solve_part1("abc\n".as_bytes(), &Params::default())
+        );
+    }
+
+    #[test]
+    fn uses_configured_marker_sizes() {
+        let params = Params {
+            packet_marker_size: 2,
+            message_marker_size: 3,
+        };
+
+        assert_eq!(Ok(3), solve_part1("aabc".as_bytes(), &params));
+        assert_eq!(Ok(4), solve_part2("aabc".as_bytes(), &params));
+    }
+
+    #[test]
+    fn returns_answer_for_part1() {
+        assert_eq!(Ok(7), run_part1("examples/day6.txt"));
+    }
+
+    #[test]
+    fn returns_answer_for_part2() {
+        assert_eq!(Ok(19), run_part2("examples/day6.txt"));
     }
 }
@@ -1,32 +1,91 @@
+use std::collections::HashMap;
+
 use itertools::Itertools;
 
 use super::lib::Slideable;
+use crate::trace;
 
-const PACKET_MARKER_SIZE: usize = 4;
-const MESSAGE_MARKER_SIZE: usize = 14;
+pub const PACKET_MARKER_SIZE: usize = 4;
+pub const MESSAGE_MARKER_SIZE: usize = 14;
 
 pub struct DataReader {
-    data: String,
+    data: Vec<char>,
+    packet_marker_size: usize,
+    message_marker_size: usize,
 }
 
 impl DataReader {
     pub fn new(data: String) -> Self {
-        DataReader { data }
+        DataReader {
+            data: data.chars().collect(),
+            packet_marker_size: PACKET_MARKER_SIZE,
+            message_marker_size: MESSAGE_MARKER_SIZE,
+        }
+    }
+
+    pub fn with_marker_sizes(self, packet_marker_size: usize, message_marker_size: usize) -> Self {
+        DataReader {
+            packet_marker_size,
+            message_marker_size,
+            ..self
+        }
     }
 
     pub fn find_start_of_packet(&self) -> Option<StartOfPacket> {
-        self.find_start_base(PACKET_MARKER_SIZE)
+        self.find_start_base(self.packet_marker_size)
             .map(|x| StartOfPacket { start_base: x })
     }
 
     pub fn find_start_of_message(&self) -> Option<StartOfMessage> {
-        self.find_start_base(MESSAGE_MARKER_SIZE)
+        self.find_start_base(self.message_marker_size)
+            .map(|x| StartOfMessage { start_base: x })
+    }
+
+    pub fn find_start_of_packet_linear(&self) -> Option<StartOfPacket> {
+        self.find_start_base_linear(self.packet_marker_size)
+            .map(|x| StartOfPacket { start_base: x })
+    }
+
+    pub fn find_start_of_message_linear(&self) -> Option<StartOfMessage> {
+        self.find_start_base_linear(self.message_marker_size)
             .map(|x| StartOfMessage { start_base: x })
     }
 
+    /// Same as `find_start_base`, but in a single pass. The window of distinct characters is shrunk past the last
+    /// occurrence of every repeated character instead of checking each window for duplicates.
+    fn find_start_base_linear(&self, marker_size: usize) -> Option<StartBase> {
+        let mut last_seen = HashMap::new();
+        let mut window_start = 0;
+        for (i, &char) in self.data.iter().enumerate() {
+            if let Some(previous) = last_seen.insert(char, i) {
+                window_start = window_start.max(previous + 1);
+            }
+
+            if i + 1 - window_start >= marker_size {
+                return Some(StartBase {
+                    chars_processed: i + 1,
+                });
+            }
+        }
+
+        None
+    }
+
     fn find_start_base(&self, marker_size: usize) -> Option<StartBase> {
-        for (i, window) in self.data.sliding_window_iter(marker_size).enumerate() {
-            if window.chars().duplicates().next().is_none() {
+        for (i, window) in self
+            .data
+            .as_slice()
+            .sliding_window_iter(marker_size)
+            .enumerate()
+        {
+            if window.iter().duplicates().next().is_none() {
+                trace::step(|| {
+                    format!(
+                        "marker '{}' ends after {} characters",
+                        window.iter().collect::<String>(),
+                        i + marker_size
+                    )
+                });
                 return Some(StartBase {
                     chars_processed: i + marker_size,
                 });
@@ -101,4 +160,34 @@ mod tests {
         let reader3 = DataReader::new("bvw".to_string());
         assert_eq!(None, reader3.find_start_of_message());
     }
+
+    #[test]
+    fn finds_same_markers_in_linear_time() {
+        for data in [
+            "bvwbjplbgvbhsrlpgdmjqwftvncz",
+            "nppdvjthqldpwncqszvftbrmjlhg",
+            "abcabcab",
+        ] {
+            let reader = DataReader::new(data.to_string());
+            assert_eq!(
+                reader.find_start_of_packet(),
+                reader.find_start_of_packet_linear()
+            );
+            assert_eq!(
+                reader.find_start_of_message(),
+                reader.find_start_of_message_linear()
+            );
+        }
+    }
+
+    #[test]
+    fn counts_multibyte_chars() {
+        let reader = DataReader::new("ääöüß".to_string());
+        assert_eq!(
+            Some(5),
+            reader
+                .find_start_of_packet()
+                .map(|x| x.get_chars_processed())
+        );
+    }
 }
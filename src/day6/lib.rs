@@ -1,5 +1,8 @@
 pub trait Slideable {
     fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
     fn slice(&self, start: usize, end: usize) -> Self;
     fn sliding_window_iter(self, n: usize) -> SlidingWindow<Self>
     where
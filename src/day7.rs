@@ -1,37 +1,91 @@
-mod cli_parser;
-mod command_parser;
-mod file_system;
+pub mod cli_parser;
+pub mod command_parser;
+pub mod file_system;
 
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
+use std::io::BufRead;
 
-use self::cli_parser::CliParser;
+use serde::Deserialize;
 
-pub fn run(path: &str) -> (usize, usize) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let mut lines_iter = reader.lines().map(|x| x.unwrap()).peekable();
+use self::{cli_parser::CliParser, file_system::FileSystem};
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+use crate::trace;
 
-    let fs = CliParser::parse(&mut lines_iter).unwrap();
-    let small_dirs_sum = fs
-        .depth_first_dirs_iter()
-        .map(|x| fs.dir_size(x))
-        .filter(|x| *x <= 100000)
-        .sum();
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// Largest size of a dir counted in part 1.
+    pub max_small_dir_size: usize,
+    pub disk_size: usize,
+    /// Free space the update needs in part 2.
+    pub required_free_space: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            max_small_dir_size: 100000,
+            disk_size: 70000000,
+            required_free_space: 30000000,
+        }
+    }
+}
+
+pub fn run_part1(path: &str) -> Result<usize, Error> {
+    solve_part1(input::open(path)?, &Params::default())
+}
+
+pub fn run_part2(path: &str) -> Result<usize, Error> {
+    solve_part2(input::open(path)?, &Params::default())
+}
+
+pub fn solve_part1<R: BufRead>(reader: R, params: &Params) -> Result<usize, Error> {
+    let fs = parse_file_system(reader)?;
+
+    let mut sum: usize = 0;
+    for dir in fs.depth_first_dirs_iter() {
+        let size = fs.dir_size(dir);
+        if size <= params.max_small_dir_size {
+            trace::step(|| format!("{} has size {}, counted", fs.dir_path(dir), size));
+            sum = sum
+                .checked_add(size)
+                .ok_or_else(|| Error::NoSolution("sum of small dir sizes overflows".to_string()))?;
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn solve_part2<R: BufRead>(reader: R, params: &Params) -> Result<usize, Error> {
+    let fs = parse_file_system(reader)?;
 
     let root_dir = fs.dirs_iter().next().unwrap();
-    let free_space = 70000000 - fs.dir_size(root_dir);
-    let space_needed = 30000000 - free_space;
-    let smallest_dir_to_remove = fs
+    let free_space = params
+        .disk_size
+        .checked_sub(fs.dir_size(root_dir))
+        .ok_or_else(|| Error::NoSolution("files don't fit on the disk".to_string()))?;
+    let space_needed = params.required_free_space.saturating_sub(free_space);
+    trace::step(|| format!("{} free, {} more needed", free_space, space_needed));
+
+    let (dir, size) = fs
         .depth_first_dirs_iter()
-        .map(|x| fs.dir_size(x))
-        .filter(|x| *x >= space_needed)
-        .min()
-        .unwrap();
+        .map(|x| (x, fs.dir_size(x)))
+        .filter(|(_, size)| *size >= space_needed)
+        .min_by_key(|(_, size)| *size)
+        .ok_or_else(|| Error::NoSolution("no dir frees up enough space".to_string()))?;
+    trace::step(|| format!("deleting {} frees up {}", fs.dir_path(dir), size));
 
-    (small_dirs_sum, smallest_dir_to_remove)
+    Ok(size)
+}
+
+fn parse_file_system<R: BufRead>(reader: R) -> Result<FileSystem, Error> {
+    let lines = input::read_lines(reader)?;
+
+    let result = CliParser::parse(&mut lines.into_iter().peekable())?;
+    timing::enter(Phase::Solve);
+
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -39,8 +93,59 @@ mod tests {
     use super::*;
 
     #[test]
-    fn returns_answer() {
-        let result = run("inputs/day7.txt");
-        println!("{:?}", result);
+    fn returns_error_if_sum_overflows() {
+        let params = Params {
+            max_small_dir_size: usize::MAX,
+            ..Params::default()
+        };
+
+        assert_eq!(
+            Err(Error::NoSolution(
+                "sum of small dir sizes overflows".to_string()
+            )),
+            solve_part1(
+                "$ cd /\n$ ls\ndir a\n18446744073709551615 b\n$ cd a\n$ ls\n1 c\n".as_bytes(),
+                &params
+            )
+        );
+    }
+
+    #[test]
+    fn returns_error_if_files_exceed_disk() {
+        assert_eq!(
+            Err(Error::NoSolution("files don't fit on the disk".to_string())),
+            solve_part2("$ cd /\n$ ls\n70000001 a\n".as_bytes(), &Params::default())
+        );
+    }
+
+    #[test]
+    fn handles_enough_free_space() {
+        assert_eq!(
+            Ok(0),
+            solve_part2("$ cd /\n$ ls\ndir a\n".as_bytes(), &Params::default())
+        );
+    }
+
+    #[test]
+    fn uses_configured_sizes() {
+        let input = "$ cd /\n$ ls\ndir a\n5 b\n$ cd a\n$ ls\n10 c\n";
+        let params = Params {
+            max_small_dir_size: 10,
+            disk_size: 20,
+            required_free_space: 10,
+        };
+
+        assert_eq!(Ok(10), solve_part1(input.as_bytes(), &params));
+        assert_eq!(Ok(10), solve_part2(input.as_bytes(), &params));
+    }
+
+    #[test]
+    fn returns_answer_for_part1() {
+        assert_eq!(Ok(95437), run_part1("examples/day7.txt"));
+    }
+
+    #[test]
+    fn returns_answer_for_part2() {
+        assert_eq!(Ok(24933642), run_part2("examples/day7.txt"));
     }
 }
@@ -1,18 +1,29 @@
 use std::iter::Peekable;
 
+use tracing::{debug, trace, trace_span};
+
 use super::command_parser::CommandParser;
 use super::file_system::FileSystem;
+use crate::error::Error;
 
 pub struct CliParser {}
 
 impl CliParser {
     pub fn parse<T: Iterator<Item = String>>(
         iterator: &mut Peekable<T>,
-    ) -> Result<FileSystem, String> {
+    ) -> Result<FileSystem, Error> {
         let mut result = FileSystem::new();
+        let mut lines_parsed = 0;
         while !Self::on_last_line(iterator) {
-            let command = CommandParser::parse(iterator)?;
-            command.update_fs(&mut result)?;
+            let _span = trace_span!("command", line = lines_parsed + 1).entered();
+            let command = CommandParser::parse(iterator)
+                .map_err(|e| e.offset_line(lines_parsed))
+                .inspect_err(|e| debug!(error = %e, "command not parsed"))?;
+            command
+                .update_fs(&mut result)
+                .inspect_err(|e| debug!(error = %e, ?command, "command not applied"))?;
+            trace!(?command, "command applied");
+            lines_parsed += command.line_count();
         }
 
         Ok(result)
@@ -73,4 +84,25 @@ mod tests {
             .collect();
         assert_eq!(expected_files, result);
     }
+
+    #[test]
+    fn returns_error_with_line_number() {
+        let mut iter = vec![
+            "$ cd /".to_string(),
+            "$ ls".to_string(),
+            "dir a".to_string(),
+            "$ cd a".to_string(),
+            "$ ls".to_string(),
+            "x b".to_string(),
+        ]
+        .into_iter()
+        .peekable();
+
+        assert_eq!(
+            Error::parse("invalid size 'x' in line 'x b'")
+                .at_line(6)
+                .at_column(1),
+            CliParser::parse(&mut iter).err().unwrap()
+        );
+    }
 }
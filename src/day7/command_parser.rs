@@ -1,20 +1,19 @@
 use std::iter::Peekable;
 
 use super::file_system::FileSystem;
+use crate::error::Error;
 
 pub struct CommandParser {}
 
 impl CommandParser {
     const COMMAND_LINE_PREFIX: &'static str = "$ ";
 
-    pub fn parse<T: Iterator<Item = String>>(
-        iterator: &mut Peekable<T>,
-    ) -> Result<Command, String> {
+    pub fn parse<T: Iterator<Item = String>>(iterator: &mut Peekable<T>) -> Result<Command, Error> {
         let line = iterator
             .next()
-            .ok_or_else(|| "empty iterator passed".to_string())?;
+            .ok_or_else(|| Error::parse("empty iterator passed"))?;
         if !Self::command_line(&line) {
-            return Err(format!("not a command line: '{}'", line));
+            return Err(Error::parse(format!("not a command line: '{}'", line)).at_line(1));
         }
 
         let content = &line[Self::COMMAND_LINE_PREFIX.len()..];
@@ -24,7 +23,11 @@ impl CommandParser {
         match *name {
             "cd" => {
                 if args.len() != 1 {
-                    return Err(format!("expected exactly 1 param in line '{}'", line));
+                    return Err(Error::parse(format!(
+                        "expected exactly 1 param in line '{}'",
+                        line
+                    ))
+                    .at_line(1));
                 }
 
                 Ok(Command::Cd(CdCommand {
@@ -35,12 +38,19 @@ impl CommandParser {
                 let mut output = Vec::new();
                 while !Self::on_last_output_line(iterator) {
                     let output_line = iterator.next().unwrap();
-                    output.push(output_line.try_into()?);
+                    let entry = output_line
+                        .try_into()
+                        .map_err(|e: Error| e.at_line(output.len() + 2))?;
+                    output.push(entry);
                 }
 
                 Ok(Command::Ls(LsCommand { output }))
             }
-            _ => Err(format!("invalid command '{}' in line '{}'", name, line)),
+            _ => Err(
+                Error::parse(format!("invalid command '{}' in line '{}'", name, line))
+                    .at_line(1)
+                    .at_column(Self::COMMAND_LINE_PREFIX.len() + 1),
+            ),
         }
     }
 
@@ -63,12 +73,19 @@ pub enum Command {
 }
 
 impl Command {
-    pub fn update_fs(&self, fs: &mut FileSystem) -> Result<(), String> {
+    pub fn update_fs(&self, fs: &mut FileSystem) -> Result<(), Error> {
         match self {
             Command::Cd(command) => command.update_fs(fs),
             Command::Ls(command) => command.update_fs(fs),
         }
     }
+
+    pub fn line_count(&self) -> usize {
+        match self {
+            Command::Cd(_) => 1,
+            Command::Ls(command) => 1 + command.output.len(),
+        }
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -77,7 +94,7 @@ pub struct CdCommand {
 }
 
 impl CdCommand {
-    fn update_fs(&self, fs: &mut FileSystem) -> Result<(), String> {
+    fn update_fs(&self, fs: &mut FileSystem) -> Result<(), Error> {
         fs.cd(&self.dir)
     }
 }
@@ -88,7 +105,7 @@ pub struct LsCommand {
 }
 
 impl LsCommand {
-    fn update_fs(&self, fs: &mut FileSystem) -> Result<(), String> {
+    fn update_fs(&self, fs: &mut FileSystem) -> Result<(), Error> {
         for entry in self.output.iter() {
             match entry {
                 FsEntry::Dir { name } => fs.add_dir(name.clone())?,
@@ -111,15 +128,15 @@ impl FsEntry {
 }
 
 impl TryFrom<String> for FsEntry {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let parts: Vec<_> = value.split(' ').collect();
         if parts.len() != 2 {
-            return Err(format!(
+            return Err(Error::parse(format!(
                 "expected exactly 2 space separated parts in line '{}'",
                 value
-            ));
+            )));
         }
 
         let name = parts[1].to_string();
@@ -127,9 +144,10 @@ impl TryFrom<String> for FsEntry {
         if value.starts_with(Self::DIR_LINE_PREFIX) {
             Ok(Self::Dir { name })
         } else {
-            let size: usize = parts[0]
-                .parse()
-                .map_err(|_| format!("invalid size '{}' in line '{}'", parts[0], value))?;
+            let size: usize = parts[0].parse().map_err(|_| {
+                Error::parse(format!("invalid size '{}' in line '{}'", parts[0], value))
+                    .at_column(1)
+            })?;
             Ok(Self::File { name, size })
         }
     }
@@ -181,25 +199,29 @@ mod tests {
     fn handles_general_errors() {
         let mut empty_iter = iter::empty::<String>().peekable();
         assert_eq!(
-            Err("empty iterator passed".to_string()),
+            Err(Error::parse("empty iterator passed")),
             CommandParser::parse(&mut empty_iter)
         );
 
         let mut iter = command_string_to_iter("cd dir".to_string());
         assert_eq!(
-            Err("not a command line: 'cd dir'".to_string()),
+            Err(Error::parse("not a command line: 'cd dir'").at_line(1)),
             CommandParser::parse(&mut iter)
         );
 
         iter = command_string_to_iter("$ ".to_string());
         assert_eq!(
-            Err("invalid command '' in line '$ '".to_string()),
+            Err(Error::parse("invalid command '' in line '$ '")
+                .at_line(1)
+                .at_column(3)),
             CommandParser::parse(&mut iter)
         );
 
         iter = command_string_to_iter("$ cp a b".to_string());
         assert_eq!(
-            Err("invalid command 'cp' in line '$ cp a b'".to_string()),
+            Err(Error::parse("invalid command 'cp' in line '$ cp a b'")
+                .at_line(1)
+                .at_column(3)),
             CommandParser::parse(&mut iter)
         );
     }
@@ -208,13 +230,13 @@ mod tests {
     fn handles_cd_command_errors() {
         let mut iter = command_string_to_iter("$ cd".to_string());
         assert_eq!(
-            Err("expected exactly 1 param in line '$ cd'".to_string()),
+            Err(Error::parse("expected exactly 1 param in line '$ cd'").at_line(1)),
             CommandParser::parse(&mut iter)
         );
 
         iter = command_string_to_iter("$ cd dir dir2".to_string());
         assert_eq!(
-            Err("expected exactly 1 param in line '$ cd dir dir2'".to_string()),
+            Err(Error::parse("expected exactly 1 param in line '$ cd dir dir2'").at_line(1)),
             CommandParser::parse(&mut iter)
         );
     }
@@ -225,7 +247,7 @@ mod tests {
             .into_iter()
             .peekable();
         assert_eq!(
-            Err("expected exactly 2 space separated parts in line '123'".to_string()),
+            Err(Error::parse("expected exactly 2 space separated parts in line '123'").at_line(3)),
             CommandParser::parse(&mut iter)
         );
 
@@ -237,7 +259,10 @@ mod tests {
         .into_iter()
         .peekable();
         assert_eq!(
-            Err("expected exactly 2 space separated parts in line '123 b.txt c.txt'".to_string()),
+            Err(
+                Error::parse("expected exactly 2 space separated parts in line '123 b.txt c.txt'")
+                    .at_line(3)
+            ),
             CommandParser::parse(&mut iter)
         );
 
@@ -249,7 +274,9 @@ mod tests {
         .into_iter()
         .peekable();
         assert_eq!(
-            Err("invalid size '-123' in line '-123 b.txt'".to_string()),
+            Err(Error::parse("invalid size '-123' in line '-123 b.txt'")
+                .at_line(3)
+                .at_column(1)),
             CommandParser::parse(&mut iter)
         );
 
@@ -261,7 +288,9 @@ mod tests {
         .into_iter()
         .peekable();
         assert_eq!(
-            Err("invalid size '123.1' in line '123.1 b.txt'".to_string()),
+            Err(Error::parse("invalid size '123.1' in line '123.1 b.txt'")
+                .at_line(3)
+                .at_column(1)),
             CommandParser::parse(&mut iter)
         );
     }
@@ -2,6 +2,8 @@ use std::collections::VecDeque;
 use std::collections::{hash_map::Entry, HashMap};
 use std::path::PathBuf;
 
+use crate::error::Error;
+
 const ROOT_DIR_ID: usize = 0;
 
 pub struct FileSystem {
@@ -19,7 +21,7 @@ impl FileSystem {
         }
     }
 
-    pub fn add_dir(&mut self, name: String) -> Result<(), String> {
+    pub fn add_dir(&mut self, name: String) -> Result<(), Error> {
         let new_entry_index = self.dirs.len();
         let current_dir = &mut self.dirs[self.current_dir_id];
         match current_dir.dir_lookup.entry(name.clone()) {
@@ -29,14 +31,14 @@ impl FileSystem {
                     .push(Dir::new(name.clone(), Some(self.current_dir_id)));
             }
             Entry::Occupied(_) => {
-                return Err(format!("dir '{}' already exists", name));
+                return Err(Error::DuplicateDir(name));
             }
         }
 
         Ok(())
     }
 
-    pub fn add_file(&mut self, name: String, size: usize) -> Result<(), String> {
+    pub fn add_file(&mut self, name: String, size: usize) -> Result<(), Error> {
         let new_entry_index = self.files.len();
         let current_dir = &mut self.dirs[self.current_dir_id];
         match current_dir.file_lookup.entry(name.clone()) {
@@ -49,14 +51,14 @@ impl FileSystem {
                 });
             }
             Entry::Occupied(_) => {
-                return Err(format!("file '{}' already exists", name.clone()));
+                return Err(Error::DuplicateFile(name));
             }
         }
 
         Ok(())
     }
 
-    pub fn cd(&mut self, dir: &str) -> Result<(), String> {
+    pub fn cd(&mut self, dir: &str) -> Result<(), Error> {
         match dir {
             "/" => {
                 self.current_dir_id = ROOT_DIR_ID;
@@ -73,7 +75,7 @@ impl FileSystem {
                 self.current_dir_id = *current_dir
                     .dir_lookup
                     .get(dir)
-                    .ok_or_else(|| format!("dir '{}' doesn\'t exist", dir))?;
+                    .ok_or_else(|| Error::MissingDir(dir.to_string()))?;
             }
         }
 
@@ -88,39 +90,36 @@ impl FileSystem {
         self.dirs.iter()
     }
 
-    #[allow(dead_code)]
     pub fn depth_first_files_iter(&self) -> impl Iterator<Item = &File> {
         DepthFirstFiles::new(self, DepthFirstDirs::new(self))
     }
 
-    #[allow(dead_code)]
     pub fn files_iter(&self) -> impl Iterator<Item = &File> {
         self.files.iter()
     }
 
+    /// Total size of the files in `dir` and its subdirectories, saturating instead of overflowing.
     pub fn dir_size(&self, dir: &Dir) -> usize {
-        let mut result = 0;
+        let mut result: usize = 0;
 
         for id in dir.file_lookup.values() {
             let file = &self.files[*id];
-            result += file.size;
+            result = result.saturating_add(file.size);
         }
 
         for id in dir.dir_lookup.values() {
             let dir = &self.dirs[*id];
-            result += self.dir_size(dir);
+            result = result.saturating_add(self.dir_size(dir));
         }
 
         result
     }
 
-    #[allow(dead_code)]
     pub fn dir_path(&self, dir: &Dir) -> String {
         let path_buf: PathBuf = self.dir_path_buf(dir);
         path_buf.to_string_lossy().to_string()
     }
 
-    #[allow(dead_code)]
     pub fn file_path(&self, file: &File) -> String {
         let parent_dir = &self.dirs[file.parent_id];
         let mut path_buf: PathBuf = self.dir_path_buf(parent_dir);
@@ -129,7 +128,6 @@ impl FileSystem {
         path_buf.to_string_lossy().to_string()
     }
 
-    #[allow(dead_code)]
     fn dir_path_buf(&self, dir: &Dir) -> PathBuf {
         let mut parts = vec![dir.name.clone()];
 
@@ -143,6 +141,12 @@ impl FileSystem {
     }
 }
 
+impl Default for FileSystem {
+    fn default() -> Self {
+        FileSystem::new()
+    }
+}
+
 #[derive(PartialEq, Debug)]
 pub struct Dir {
     name: String,
@@ -465,7 +469,7 @@ mod tests {
         fs.add_dir("a".to_string()).unwrap();
 
         assert_eq!(
-            Err("dir 'invalid' doesn\'t exist".to_string()),
+            Err(Error::MissingDir("invalid".to_string())),
             fs.cd("invalid")
         );
     }
@@ -477,7 +481,7 @@ mod tests {
         fs.add_file("a".to_string(), 12).unwrap();
 
         assert_eq!(
-            Err("file 'a' already exists".to_string()),
+            Err(Error::DuplicateFile("a".to_string())),
             fs.add_file("a".to_string(), 34)
         );
 
@@ -509,7 +513,7 @@ mod tests {
         // path: /
         fs.cd("..").unwrap();
         assert_eq!(
-            Err("dir 'a' already exists".to_string()),
+            Err(Error::DuplicateDir("a".to_string())),
             fs.add_dir("a".to_string())
         );
 
@@ -1,29 +1,70 @@
-mod tree_grid;
+pub mod tree_grid;
 
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
+use std::io::BufRead;
 
 use self::tree_grid::TreeGrid;
+use crate::error::Error;
+use crate::input;
+use crate::timing::{self, Phase};
+use crate::trace;
 
-pub fn run(path: &str) -> (usize, usize) {
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
-    let lines_iter = reader.lines().map(|x| x.unwrap());
+pub fn run_part1(path: &str) -> Result<usize, Error> {
+    solve_part1(input::open(path)?)
+}
+
+pub fn run_part2(path: &str) -> Result<usize, Error> {
+    solve_part2(input::open(path)?)
+}
 
-    let tree_grid = TreeGrid::parse(lines_iter).unwrap();
-    let visible_from_outside_count = tree_grid
+pub fn solve_part1<R: BufRead>(reader: R) -> Result<usize, Error> {
+    let tree_grid = parse_tree_grid(reader)?;
+
+    let visible = tree_grid
         .tree_iter()
         .filter(|x| tree_grid.tree_visible(x))
         .count();
-    let highest_scenic_score = tree_grid
+    trace::step(|| {
+        format!(
+            "{} of {} trees are visible",
+            visible,
+            tree_grid.tree_iter().count()
+        )
+    });
+
+    Ok(visible)
+}
+
+/// Map of the trees visible from outside the grid, see `TreeGrid::visibility_map`.
+pub fn draw_visibility<R: BufRead>(reader: R) -> Result<String, Error> {
+    Ok(parse_tree_grid(reader)?.visibility_map())
+}
+
+pub fn solve_part2<R: BufRead>(reader: R) -> Result<usize, Error> {
+    let tree_grid = parse_tree_grid(reader)?;
+
+    let (tree, score) = tree_grid
         .tree_iter()
-        .map(|x| tree_grid.scenic_score(x))
-        .max()
-        .unwrap();
+        .map(|x| (x, tree_grid.scenic_score(&x)))
+        .max_by_key(|(_, score)| *score)
+        .ok_or_else(|| Error::NoSolution("tree grid is empty".to_string()))?;
+    trace::step(|| {
+        let [up, down, left, right] = tree_grid.viewing_distances(&tree);
+        format!(
+            "best is the {}, it sees {} up, {} down, {} left and {} right",
+            tree, up, down, left, right
+        )
+    });
 
-    (visible_from_outside_count, highest_scenic_score)
+    Ok(score)
+}
+
+fn parse_tree_grid<R: BufRead>(reader: R) -> Result<TreeGrid, Error> {
+    let lines = input::read_lines(reader)?;
+
+    let result = TreeGrid::parse(lines.into_iter())?;
+    timing::enter(Phase::Solve);
+
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -31,8 +72,20 @@ mod tests {
     use super::*;
 
     #[test]
-    fn returns_answer() {
-        let result = run("inputs/day8.txt");
-        println!("{:?}", result);
+    fn returns_error_for_empty_grid() {
+        assert_eq!(
+            Err(Error::NoSolution("tree grid is empty".to_string())),
+            solve_part2("".as_bytes())
+        );
+    }
+
+    #[test]
+    fn returns_answer_for_part1() {
+        assert_eq!(Ok(21), run_part1("examples/day8.txt"));
+    }
+
+    #[test]
+    fn returns_answer_for_part2() {
+        assert_eq!(Ok(8), run_part2("examples/day8.txt"));
     }
 }
@@ -1,110 +1,62 @@
+use std::fmt;
+
+use crate::error::Error;
+use crate::grid::{Direction, Grid, Position};
+
 #[derive(PartialEq, Debug)]
 pub struct TreeGrid {
-    storage: Vec<Vec<Tree>>,
+    heights: Grid<u32>,
 }
 
 impl TreeGrid {
-    pub fn parse<T: Iterator<Item = String>>(iter: T) -> Result<TreeGrid, String> {
-        let mut storage = Vec::new();
-        let mut line_length: Option<usize> = None;
-
-        for (row_index, line) in iter.enumerate() {
-            if let Some(len) = line_length {
-                if line.len() != len {
-                    return Err(format!(
-                        "Line length mismatch at row {}. Expected length {}, found length {}",
-                        row_index + 1,
-                        len,
-                        line.len()
-                    ));
-                }
-            } else {
-                line_length = Some(line.len());
-            }
-
-            let mut tree_line = Vec::new();
-
-            for (char_index, char) in line.chars().enumerate() {
-                match char.to_digit(10) {
-                    Some(height) => tree_line.push(Tree {
-                        height,
-                        row: row_index + 1,
-                        column: char_index + 1,
-                    }),
-                    None => {
-                        return Err(format!(
-                            "Invalid character '{}' at row {}, position {}",
-                            char,
-                            row_index + 1,
-                            char_index + 1
-                        ))
-                    }
-                }
-            }
+    pub fn parse<T: Iterator<Item = String>>(iter: T) -> Result<TreeGrid, Error> {
+        let heights = Grid::parse(iter, |x| {
+            x.to_digit(10)
+                .ok_or_else(|| Error::parse(format!("invalid character '{}'", x)))
+        })?;
 
-            storage.push(tree_line);
-        }
-
-        Ok(TreeGrid { storage })
+        Ok(TreeGrid { heights })
     }
 
-    pub fn tree_iter(&self) -> impl Iterator<Item = &Tree> {
-        self.storage.iter().flatten()
+    pub fn tree_iter(&self) -> impl Iterator<Item = Tree> + '_ {
+        self.heights
+            .iter()
+            .map(|(position, &height)| Tree { height, position })
     }
 
     pub fn tree_visible(&self, tree: &Tree) -> bool {
-        self.left_visibility(tree).visible
-            || self.right_visibility(tree).visible
-            || self.top_visibility(tree).visible
-            || self.bottom_visibility(tree).visible
+        Direction::ORTHOGONAL
+            .into_iter()
+            .any(|x| self.check_visibility(tree, x).visible)
+    }
+
+    /// Map of the grid with visible trees drawn as `#` and hidden ones as `.`.
+    pub fn visibility_map(&self) -> String {
+        self.heights
+            .map(|position, &height| {
+                if self.tree_visible(&Tree { height, position }) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .to_string()
     }
 
     pub fn scenic_score(&self, tree: &Tree) -> usize {
-        self.left_visibility(tree).count
-            * self.right_visibility(tree).count
-            * self.top_visibility(tree).count
-            * self.bottom_visibility(tree).count
-    }
-
-    fn left_visibility(&self, tree: &Tree) -> TreeVisibility {
-        self.check_visibility(tree, (0..tree.column - 1).rev(), |i| {
-            &self.storage[tree.row - 1][i]
-        })
-    }
-
-    fn right_visibility(&self, tree: &Tree) -> TreeVisibility {
-        self.check_visibility(tree, tree.column..self.width(), |i| {
-            &self.storage[tree.row - 1][i]
-        })
-    }
-
-    fn top_visibility(&self, tree: &Tree) -> TreeVisibility {
-        self.check_visibility(tree, (0..tree.row - 1).rev(), |i| {
-            &self.storage[i][tree.column - 1]
-        })
+        self.viewing_distances(tree).iter().product()
     }
 
-    fn bottom_visibility(&self, tree: &Tree) -> TreeVisibility {
-        self.check_visibility(tree, tree.row..self.height(), |i| {
-            &self.storage[i][tree.column - 1]
-        })
+    /// Number of trees seen up, down, left and right.
+    pub fn viewing_distances(&self, tree: &Tree) -> [usize; 4] {
+        Direction::ORTHOGONAL.map(|x| self.check_visibility(tree, x).count)
     }
 
-    fn check_visibility<'a, I, F>(
-        &'a self,
-        tree: &Tree,
-        range: I,
-        tree_selector: F,
-    ) -> TreeVisibility
-    where
-        I: Iterator<Item = usize>,
-        F: Fn(usize) -> &'a Tree,
-    {
+    fn check_visibility(&self, tree: &Tree, direction: Direction) -> TreeVisibility {
         let mut count = 0;
-        for i in range {
+        for (_, &height) in self.heights.ray(tree.position, direction) {
             count += 1;
-            let checked_tree = tree_selector(i);
-            if checked_tree.height >= tree.height {
+            if height >= tree.height {
                 return TreeVisibility {
                     count,
                     visible: false,
@@ -116,21 +68,24 @@ impl TreeGrid {
             visible: true,
         }
     }
-
-    fn height(&self) -> usize {
-        self.storage.len()
-    }
-
-    fn width(&self) -> usize {
-        self.storage.first().map_or(0, |x| x.len())
-    }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub struct Tree {
     height: u32,
-    row: usize,
-    column: usize,
+    position: Position,
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tree of height {} at line {}, column {}",
+            self.height,
+            self.position.row + 1,
+            self.position.column + 1
+        )
+    }
 }
 
 struct TreeVisibility {
@@ -148,43 +103,42 @@ mod tests {
         let grid = TreeGrid::parse(lines.into_iter()).unwrap();
 
         let tree_iter = grid.tree_iter();
-        let expected_trees = vec![
+        let expected_trees = [
             Tree {
                 height: 1,
-                row: 1,
-                column: 1,
+                position: Position::new(0, 0),
             },
             Tree {
                 height: 2,
-                row: 1,
-                column: 2,
+                position: Position::new(0, 1),
             },
             Tree {
                 height: 3,
-                row: 1,
-                column: 3,
+                position: Position::new(0, 2),
             },
             Tree {
                 height: 4,
-                row: 2,
-                column: 1,
+                position: Position::new(1, 0),
             },
             Tree {
                 height: 5,
-                row: 2,
-                column: 2,
+                position: Position::new(1, 1),
             },
             Tree {
                 height: 6,
-                row: 2,
-                column: 3,
+                position: Position::new(1, 2),
             },
         ];
 
-        assert_eq!(
-            expected_trees.iter().collect::<Vec<_>>(),
-            tree_iter.collect::<Vec<_>>()
-        );
+        assert_eq!(expected_trees.to_vec(), tree_iter.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn draws_visibility_map() {
+        let lines = ["30373", "25512", "65332", "33549", "35390"].map(String::from);
+        let grid = TreeGrid::parse(lines.into_iter()).unwrap();
+
+        assert_eq!("#####\n###.#\n##.##\n#.#.#\n#####", grid.visibility_map());
     }
 
     #[test]
@@ -193,7 +147,9 @@ mod tests {
         let result = TreeGrid::parse(lines.into_iter());
 
         assert_eq!(
-            Err("Invalid character 'a' at row 2, position 3".to_string()),
+            Err(Error::parse("invalid character 'a'")
+                .at_line(2)
+                .at_column(3)),
             result
         );
     }
@@ -203,7 +159,7 @@ mod tests {
         let lines = vec!["123".to_string(), "4567".to_string()];
         let result = TreeGrid::parse(lines.into_iter());
         assert_eq!(
-            Err("Line length mismatch at row 2. Expected length 3, found length 4".to_string()),
+            Err(Error::parse("line length mismatch, expected length 3, found length 4").at_line(2)),
             result
         );
     }
@@ -239,19 +195,24 @@ mod tests {
         let grid = TreeGrid::parse(lines.into_iter()).unwrap();
         let trees: Vec<_> = grid.tree_iter().collect();
 
-        assert_eq!(4, grid.scenic_score(trees[7]));
-        assert_eq!(8, grid.scenic_score(trees[17]));
+        assert_eq!(4, grid.scenic_score(&trees[7]));
+        assert_eq!(8, grid.scenic_score(&trees[17]));
+        assert_eq!([2, 1, 2, 2], grid.viewing_distances(&trees[17]));
+        assert_eq!(
+            "tree of height 5 at line 4, column 3",
+            trees[17].to_string()
+        );
     }
 
     fn assert_all_visible(lines: Vec<String>) {
         let grid = TreeGrid::parse(lines.into_iter()).unwrap();
         let trees: Vec<_> = grid.tree_iter().collect();
-        assert!(trees.into_iter().all(|x| grid.tree_visible(x)));
+        assert!(trees.iter().all(|x| grid.tree_visible(x)));
     }
 
     fn center_visible(lines: Vec<String>) -> bool {
         let grid = TreeGrid::parse(lines.into_iter()).unwrap();
         let trees: Vec<_> = grid.tree_iter().collect();
-        grid.tree_visible(trees[4])
+        grid.tree_visible(&trees[4])
     }
 }
@@ -0,0 +1,249 @@
+use std::{fmt, fs};
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::generate;
+use crate::registry::{self, Registry};
+use crate::solver::{self, Answer, Part, Solver, Variant};
+
+/// Input on which a variant doesn't agree with the day's solver.
+#[derive(PartialEq, Debug)]
+pub struct Disagreement {
+    pub day: u32,
+    pub part: Part,
+    pub variant: &'static str,
+    /// Smallest input found that still shows the disagreement.
+    pub input: String,
+    pub expected: Result<Answer, Error>,
+    pub actual: Result<Answer, Error>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {}, part {}, {}: expected {}, got {} on input:\n{}",
+            self.day,
+            self.part,
+            self.variant,
+            describe(&self.expected),
+            describe(&self.actual),
+            self.input
+        )
+    }
+}
+
+fn describe(result: &Result<Answer, Error>) -> String {
+    match result {
+        Ok(answer) => answer.to_string(),
+        Err(error) => format!("error '{}'", error),
+    }
+}
+
+/// Variants are free to report different errors, so they agree with the solver as long as both fail.
+fn agrees(expected: &Result<Answer, Error>, actual: &Result<Answer, Error>) -> bool {
+    match (expected, actual) {
+        (Ok(expected), Ok(actual)) => expected == actual,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+/// Runs the variant and the solver on `input`, returning the minimized disagreement if they don't agree.
+pub fn compare(solver: &dyn Solver, variant: &Variant, input: &str) -> Option<Disagreement> {
+    let run = |input: &str| {
+        let expected = solver::solve_guarded(solver, variant.part, &mut input.as_bytes());
+        let actual = variant.solve_guarded(&mut input.as_bytes());
+        (expected, actual)
+    };
+
+    let (expected, actual) = run(input);
+    if agrees(&expected, &actual) {
+        return None;
+    }
+
+    let input = minimize(input, |x| {
+        let (expected, actual) = run(x);
+        !agrees(&expected, &actual)
+    });
+    let (expected, actual) = run(&input);
+
+    Some(Disagreement {
+        day: variant.day,
+        part: variant.part,
+        variant: variant.name,
+        input,
+        expected,
+        actual,
+    })
+}
+
+/// Shrinks `input` as long as `fails` holds for it, first removing whole lines and then single characters.
+pub fn minimize<F: FnMut(&str) -> bool>(input: &str, mut fails: F) -> String {
+    let lines = shrink(
+        input.split_inclusive('\n').map(String::from).collect(),
+        &mut fails,
+    );
+    let chars = shrink(
+        lines.concat().chars().map(String::from).collect(),
+        &mut fails,
+    );
+
+    chars.concat()
+}
+
+/// Removes chunks of `units` for which the rest still fails, halving the chunk size once none can be removed.
+fn shrink<F: FnMut(&str) -> bool>(mut units: Vec<String>, fails: &mut F) -> Vec<String> {
+    let mut chunk_size = (units.len() / 2).max(1);
+    while chunk_size > 0 && !units.is_empty() {
+        let mut removed = false;
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + chunk_size).min(units.len());
+            let candidate = units[..start].concat() + &units[end..].concat();
+            if fails(&candidate) {
+                units.drain(start..end);
+                removed = true;
+            } else {
+                start += chunk_size;
+            }
+        }
+
+        if !removed {
+            chunk_size /= 2;
+        }
+    }
+
+    units
+}
+
+/// Inputs to compare a day's variants on: its personal input at the path of `config` and its example if they
+/// exist, followed by `count` generated ones of up to `size`, seeded from `seed` upwards and wrapping around.
+pub fn inputs(config: &Config, day: u32, count: usize, size: usize, seed: u64) -> Vec<String> {
+    let mut result: Vec<String> = [config.input_path(day), registry::example_input_path(day)]
+        .iter()
+        .filter_map(|x| fs::read_to_string(x).ok())
+        .collect();
+
+    result.extend(
+        (0..count)
+            .filter_map(|x| generate::generate(day, x % size + 1, seed.wrapping_add(x as u64))),
+    );
+
+    result
+}
+
+/// Compares the variant with its solver on `inputs` up to the first disagreement.
+pub fn check_variant(
+    solver: &dyn Solver,
+    variant: &Variant,
+    inputs: &[String],
+) -> Option<Disagreement> {
+    inputs.iter().find_map(|x| compare(solver, variant, x))
+}
+
+/// Compares every variant of `day` with its solver on all `inputs`, reporting the first disagreement of each.
+pub fn check_day(registry: &Registry, day: u32, inputs: &[String]) -> Vec<Disagreement> {
+    let Some(solver) = registry.get(day) else {
+        return Vec::new();
+    };
+
+    Part::ALL
+        .iter()
+        .flat_map(|&part| registry.variants(day, part))
+        .filter_map(|variant| check_variant(solver, variant, inputs))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::day6;
+
+    #[test]
+    fn minimizes_lines_then_chars() {
+        let input = "abc\nxyz\ndef\nx\n";
+
+        assert_eq!("x", minimize(input, |x| x.contains('x')));
+        assert_eq!("yz\n", minimize(input, |x| x.contains("yz\n")));
+    }
+
+    #[test]
+    fn keeps_input_that_cannot_shrink() {
+        assert_eq!("ab", minimize("ab", |x| x == "ab"));
+    }
+
+    #[test]
+    fn reports_minimized_disagreement() {
+        let registry = Registry::new();
+        let broken = Variant {
+            day: 6,
+            part: Part::One,
+            name: "broken",
+            solve: |x| day6::solve_part1(x, &day6::Params::default()).map(|x| Answer::from(x + 1)),
+        };
+
+        let disagreement = compare(
+            registry.get(6).unwrap(),
+            &broken,
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n",
+        )
+        .unwrap();
+
+        assert_eq!(4, disagreement.input.chars().count());
+        assert_eq!(Ok(Answer::Number(4)), disagreement.expected);
+        assert_eq!(Ok(Answer::Number(5)), disagreement.actual);
+        assert!(disagreement
+            .to_string()
+            .starts_with("Day 6, part 1, broken: expected 4, got 5 on input:\n"));
+    }
+
+    #[test]
+    fn agrees_if_both_fail() {
+        let registry = Registry::new();
+        let variant = registry.variants(6, Part::Two).next().unwrap();
+
+        assert_eq!(None, compare(registry.get(6).unwrap(), variant, "abc"));
+    }
+
+    #[test]
+    fn reads_configured_input() {
+        let path = env::temp_dir().join(format!("aoc2022-differential-{}.txt", process::id()));
+        fs::write(&path, "abcd\n").unwrap();
+        let config =
+            Config::parse("", &[format!("inputs.days.day6=\"{}\"", path.display())]).unwrap();
+
+        let inputs = inputs(&config, 6, 0, 1, 1);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            vec![
+                "abcd\n".to_string(),
+                fs::read_to_string("examples/day6.txt").unwrap()
+            ],
+            inputs
+        );
+    }
+
+    #[test]
+    fn wraps_seeds_around() {
+        let mut inputs = inputs(&Config::default(), 6, 2, 1, u64::MAX);
+
+        assert_eq!(generate::generate(6, 1, 0), inputs.pop());
+        assert_eq!(generate::generate(6, 1, u64::MAX), inputs.pop());
+    }
+
+    #[test]
+    fn variants_agree_on_generated_inputs() {
+        let registry = Registry::new();
+        for (day, _) in registry.iter() {
+            let inputs = inputs(&Config::default(), day, 20, 30, 1);
+            assert_eq!(
+                Vec::<Disagreement>::new(),
+                check_day(&registry, day, &inputs)
+            );
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::{fmt, io};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse {
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    DuplicateStack(String),
+    UnknownStack {
+        id: String,
+        command: String,
+    },
+    NotEnoughCrates {
+        stack: String,
+        available: usize,
+        command: String,
+    },
+    DuplicateDir(String),
+    DuplicateFile(String),
+    MissingDir(String),
+    NoSolution(String),
+    Panic(String),
+    Http(String),
+}
+
+impl Error {
+    pub fn parse<T: Into<String>>(message: T) -> Self {
+        Error::Parse {
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Sets the line of a parse error, unless it's already known.
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            Error::Parse {
+                line: None,
+                column,
+                message,
+            } => Error::Parse {
+                line: Some(line),
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+
+    /// Sets the column of a parse error, unless it's already known.
+    pub fn at_column(self, column: usize) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column: None,
+                message,
+            } => Error::Parse {
+                line,
+                column: Some(column),
+                message,
+            },
+            other => other,
+        }
+    }
+
+    /// Shifts the line of a parse error, used when a section is parsed separately from the rest of the input.
+    pub fn offset_line(self, offset: usize) -> Self {
+        match self {
+            Error::Parse {
+                line: Some(line),
+                column,
+                message,
+            } => Error::Parse {
+                line: Some(line + offset),
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+
+    /// Parse error at the position of a TOML syntax error in `content`.
+    pub fn toml(content: &str, error: &toml::de::Error) -> Self {
+        let result = Error::parse(error.message());
+        let Some(before) = error.span().and_then(|x| content.get(..x.start)) else {
+            return result;
+        };
+
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        result.at_line(line).at_column(column)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "I/O error: {}", error),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => match (line, column) {
+                (Some(line), Some(column)) => {
+                    write!(f, "line {}, column {}: {}", line, column, message)
+                }
+                (Some(line), None) => write!(f, "line {}: {}", line, message),
+                (None, Some(column)) => write!(f, "column {}: {}", column, message),
+                (None, None) => write!(f, "{}", message),
+            },
+            Error::DuplicateStack(id) => write!(f, "duplicate stack id '{}'", id),
+            Error::UnknownStack { id, command } => write!(
+                f,
+                "unknown stack id '{}' specified in command '{}'",
+                id, command
+            ),
+            Error::NotEnoughCrates {
+                stack,
+                available,
+                command,
+            } => write!(
+                f,
+                "not enough items ({}) in stack '{}' specified in command '{}'",
+                available, stack, command
+            ),
+            Error::DuplicateDir(name) => write!(f, "dir '{}' already exists", name),
+            Error::DuplicateFile(name) => write!(f, "file '{}' already exists", name),
+            Error::MissingDir(name) => write!(f, "dir '{}' doesn't exist", name),
+            Error::NoSolution(message) => write!(f, "no solution: {}", message),
+            Error::Panic(message) => write!(f, "solver panicked: {}", message),
+            Error::Http(message) => write!(f, "request failed: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+// io::Error isn't cloneable, so the clone keeps its kind and message but drops the source
+impl Clone for Error {
+    fn clone(&self) -> Self {
+        match self {
+            Error::Io(error) => Error::Io(io::Error::new(error.kind(), error.to_string())),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => Error::Parse {
+                line: *line,
+                column: *column,
+                message: message.clone(),
+            },
+            Error::DuplicateStack(id) => Error::DuplicateStack(id.clone()),
+            Error::UnknownStack { id, command } => Error::UnknownStack {
+                id: id.clone(),
+                command: command.clone(),
+            },
+            Error::NotEnoughCrates {
+                stack,
+                available,
+                command,
+            } => Error::NotEnoughCrates {
+                stack: stack.clone(),
+                available: *available,
+                command: command.clone(),
+            },
+            Error::DuplicateDir(name) => Error::DuplicateDir(name.clone()),
+            Error::DuplicateFile(name) => Error::DuplicateFile(name.clone()),
+            Error::MissingDir(name) => Error::MissingDir(name.clone()),
+            Error::NoSolution(message) => Error::NoSolution(message.clone()),
+            Error::Panic(message) => Error::Panic(message.clone()),
+            Error::Http(message) => Error::Http(message.clone()),
+        }
+    }
+}
+
+// io::Error isn't comparable, so I/O errors are considered equal if their kinds match
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Error::Io(a), Error::Io(b)) => a.kind() == b.kind(),
+            (
+                Error::Parse {
+                    line: a_line,
+                    column: a_column,
+                    message: a_message,
+                },
+                Error::Parse {
+                    line: b_line,
+                    column: b_column,
+                    message: b_message,
+                },
+            ) => a_line == b_line && a_column == b_column && a_message == b_message,
+            (Error::DuplicateStack(a), Error::DuplicateStack(b)) => a == b,
+            (
+                Error::UnknownStack {
+                    id: a_id,
+                    command: a_command,
+                },
+                Error::UnknownStack {
+                    id: b_id,
+                    command: b_command,
+                },
+            ) => a_id == b_id && a_command == b_command,
+            (
+                Error::NotEnoughCrates {
+                    stack: a_stack,
+                    available: a_available,
+                    command: a_command,
+                },
+                Error::NotEnoughCrates {
+                    stack: b_stack,
+                    available: b_available,
+                    command: b_command,
+                },
+            ) => a_stack == b_stack && a_available == b_available && a_command == b_command,
+            (Error::DuplicateDir(a), Error::DuplicateDir(b)) => a == b,
+            (Error::DuplicateFile(a), Error::DuplicateFile(b)) => a == b,
+            (Error::MissingDir(a), Error::MissingDir(b)) => a == b,
+            (Error::NoSolution(a), Error::NoSolution(b)) => a == b,
+            (Error::Panic(a), Error::Panic(b)) => a == b,
+            (Error::Http(a), Error::Http(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_parse_error_position_once() {
+        let error = Error::parse("invalid").at_line(2).at_column(3);
+        assert_eq!(
+            Error::Parse {
+                line: Some(2),
+                column: Some(3),
+                message: "invalid".to_string()
+            },
+            error
+        );
+
+        // Position set closest to the failure wins
+        assert_eq!(
+            error,
+            Error::parse("invalid")
+                .at_line(2)
+                .at_column(3)
+                .at_line(5)
+                .at_column(6)
+        );
+    }
+
+    #[test]
+    fn offsets_parse_error_line() {
+        assert_eq!(
+            Error::parse("invalid").at_line(7),
+            Error::parse("invalid").at_line(2).offset_line(5)
+        );
+        assert_eq!(
+            Error::parse("invalid"),
+            Error::parse("invalid").offset_line(5)
+        );
+    }
+
+    #[test]
+    fn leaves_other_errors_unchanged() {
+        assert_eq!(
+            Error::MissingDir("a".to_string()),
+            Error::MissingDir("a".to_string()).at_line(1).at_column(1)
+        );
+    }
+
+    #[test]
+    fn formats_parse_error_with_position() {
+        assert_eq!("invalid", Error::parse("invalid").to_string());
+        assert_eq!(
+            "line 2: invalid",
+            Error::parse("invalid").at_line(2).to_string()
+        );
+        assert_eq!(
+            "line 2, column 3: invalid",
+            Error::parse("invalid").at_line(2).at_column(3).to_string()
+        );
+    }
+
+    #[test]
+    fn compares_io_errors_by_kind() {
+        let a = Error::from(io::Error::new(io::ErrorKind::NotFound, "a"));
+        let b = Error::from(io::Error::new(io::ErrorKind::NotFound, "b"));
+        let c = Error::from(io::Error::new(io::ErrorKind::InvalidData, "a"));
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn clones_io_error_message() {
+        let error = Error::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+
+        assert_eq!(error, error.clone());
+        assert_eq!(error.to_string(), error.clone().to_string());
+    }
+}
@@ -0,0 +1,128 @@
+use fastrand::Rng;
+
+/// Characters the puzzle inputs are made of, plus a few that none of them expect.
+const ALPHABET: &[char] = &[
+    '0', '1', '9', 'a', 'z', 'A', 'Z', 'X', ' ', ' ', '\n', '\n', '\t', '\r', '-', ',', '[', ']',
+    '$', '/', '.', 'é', '€', '🎄',
+];
+
+/// Numbers at the edges of the integer types the days parse into.
+const NUMBERS: &[&str] = &[
+    "0",
+    "-1",
+    "2147483647",
+    "2147483648",
+    "4294967296",
+    "18446744073709551615",
+    "99999999999999999999",
+];
+
+/// Applies up to eight random edits to `input`. Starting from a valid input gets past the first lines of a parser
+/// far more often than pure noise does.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.usize(1..=8) {
+        match rng.u8(..7) {
+            0 => insert_chars(rng, &mut chars),
+            1 => replace_char(rng, &mut chars),
+            2 => remove_chars(rng, &mut chars),
+            3 => insert_number(rng, &mut chars),
+            4 => duplicate_line(rng, &mut chars),
+            5 => remove_line(rng, &mut chars),
+            _ => chars.truncate(rng.usize(..=chars.len())),
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+/// Random characters from the alphabet of the puzzle inputs.
+pub fn noise(rng: &mut Rng, length: usize) -> String {
+    (0..length)
+        .map(|_| ALPHABET[rng.usize(..ALPHABET.len())])
+        .collect()
+}
+
+fn insert_chars(rng: &mut Rng, chars: &mut Vec<char>) {
+    let position = rng.usize(..=chars.len());
+    let length = rng.usize(1..=4);
+    let inserted = noise(rng, length);
+    chars.splice(position..position, inserted.chars());
+}
+
+fn replace_char(rng: &mut Rng, chars: &mut [char]) {
+    if !chars.is_empty() {
+        let position = rng.usize(..chars.len());
+        chars[position] = ALPHABET[rng.usize(..ALPHABET.len())];
+    }
+}
+
+fn remove_chars(rng: &mut Rng, chars: &mut Vec<char>) {
+    let start = rng.usize(..=chars.len());
+    let end = rng.usize(start..=chars.len().min(start + 8));
+    chars.drain(start..end);
+}
+
+fn insert_number(rng: &mut Rng, chars: &mut Vec<char>) {
+    let position = rng.usize(..=chars.len());
+    let number = NUMBERS[rng.usize(..NUMBERS.len())];
+    chars.splice(position..position, number.chars());
+}
+
+/// Line of `chars` around `position` as a range, including its line break.
+fn line_range(chars: &[char], position: usize) -> (usize, usize) {
+    let start = chars[..position]
+        .iter()
+        .rposition(|&x| x == '\n')
+        .map_or(0, |x| x + 1);
+    let end = chars[position..]
+        .iter()
+        .position(|&x| x == '\n')
+        .map_or(chars.len(), |x| position + x + 1);
+
+    (start, end)
+}
+
+fn duplicate_line(rng: &mut Rng, chars: &mut Vec<char>) {
+    let (start, end) = line_range(chars, rng.usize(..=chars.len()));
+    let line: Vec<char> = chars[start..end].to_vec();
+    let position = line_range(chars, rng.usize(..=chars.len())).0;
+    chars.splice(position..position, line);
+}
+
+fn remove_line(rng: &mut Rng, chars: &mut Vec<char>) {
+    let (start, end) = line_range(chars, rng.usize(..=chars.len()));
+    chars.drain(start..end);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_mutations_for_same_seed() {
+        let input = "1000\n2000\n\n3000\n";
+
+        assert_eq!(
+            mutate(&mut Rng::with_seed(7), input),
+            mutate(&mut Rng::with_seed(7), input)
+        );
+    }
+
+    #[test]
+    fn mutates_empty_input() {
+        let mut rng = Rng::with_seed(1);
+        for _ in 0..100 {
+            mutate(&mut rng, "");
+        }
+    }
+
+    #[test]
+    fn finds_line_of_position() {
+        let chars: Vec<char> = "ab\ncd\nef".chars().collect();
+
+        assert_eq!((0, 3), line_range(&chars, 0));
+        assert_eq!((3, 6), line_range(&chars, 4));
+        assert_eq!((6, 8), line_range(&chars, 8));
+    }
+}
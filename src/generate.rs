@@ -0,0 +1,290 @@
+use std::collections::HashSet;
+
+use fastrand::Rng;
+
+/// Generates a random, solvable input for a day. `size` scales the input roughly linearly: the number of elves,
+/// rounds, rucksack groups, pairs, moves, datastream characters, directories or grid rows respectively.
+/// The same seed always yields the same input. Returns `None` for days without a generator.
+pub fn generate(day: u32, size: usize, seed: u64) -> Option<String> {
+    let mut rng = Rng::with_seed(seed);
+    let size = size.max(1);
+
+    let lines = match day {
+        1 => calories(&mut rng, size),
+        2 => strategy_guide(&mut rng, size),
+        3 => rucksacks(&mut rng, size),
+        4 => section_pairs(&mut rng, size),
+        5 => crate_moves(&mut rng, size),
+        6 => vec![datastream(&mut rng, size)],
+        7 => terminal_output(&mut rng, size),
+        8 => tree_grid(&mut rng, size),
+        _ => return None,
+    };
+
+    Some(lines.join("\n") + "\n")
+}
+
+fn calories(rng: &mut Rng, elves: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for elf in 0..elves {
+        if elf > 0 {
+            lines.push(String::new());
+        }
+        for _ in 0..rng.usize(1..=15) {
+            lines.push(rng.u32(1000..=70000).to_string());
+        }
+    }
+
+    lines
+}
+
+fn strategy_guide(rng: &mut Rng, rounds: usize) -> Vec<String> {
+    (0..rounds)
+        .map(|_| format!("{} {}", rng.char('A'..='C'), rng.char('X'..='Z')))
+        .collect()
+}
+
+/// Every rucksack has exactly one item type in both compartments and every group of three exactly one badge.
+fn rucksacks(rng: &mut Rng, groups: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for _ in 0..groups {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut items);
+        let (badge, rest) = items.split_first().unwrap();
+
+        // Rucksacks of a group draw from disjoint item types, so the badge is their only common one
+        for pool in rest.chunks(rest.len() / 3).take(3) {
+            let (duplicate, pool) = pool.split_first().unwrap();
+            let (first_pool, second_pool) = pool.split_at(pool.len() / 2);
+
+            let count = rng.usize(2..=15);
+            let mut first: Vec<char> = vec![*duplicate, *badge];
+            first.extend((0..count).map(|_| first_pool[rng.usize(..first_pool.len())]));
+            let mut second: Vec<char> = vec![*duplicate];
+            second.extend((0..count + 1).map(|_| second_pool[rng.usize(..second_pool.len())]));
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+
+            lines.push(first.into_iter().chain(second).collect());
+        }
+    }
+
+    lines
+}
+
+fn section_pairs(rng: &mut Rng, pairs: usize) -> Vec<String> {
+    let mut assignment = || {
+        let lower = rng.u32(1..=99);
+        format!("{}-{}", lower, rng.u32(lower..=99))
+    };
+
+    (0..pairs)
+        .map(|_| {
+            let first = assignment();
+            format!("{},{}", first, assignment())
+        })
+        .collect()
+}
+
+/// Drawing of nine stacks followed by moves that never take more crates than a stack holds.
+fn crate_moves(rng: &mut Rng, moves: usize) -> Vec<String> {
+    const STACKS: usize = 9;
+
+    let mut heights: Vec<usize> = (0..STACKS).map(|_| rng.usize(1..=8)).collect();
+    let max_height = *heights.iter().max().unwrap();
+
+    let mut lines = Vec::new();
+    for level in (0..max_height).rev() {
+        let cells: Vec<String> = heights
+            .iter()
+            .map(|&height| {
+                if level < height {
+                    format!("[{}]", rng.uppercase())
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect();
+        lines.push(cells.join(" "));
+    }
+    let ids: Vec<String> = (1..=STACKS).map(|x| format!(" {} ", x)).collect();
+    lines.push(ids.join(" "));
+    lines.push(String::new());
+
+    for _ in 0..moves {
+        let non_empty: Vec<usize> = (0..STACKS).filter(|&x| heights[x] > 0).collect();
+        let from = non_empty[rng.usize(..non_empty.len())];
+        let mut to = rng.usize(..STACKS - 1);
+        if to >= from {
+            to += 1;
+        }
+        let count = rng.usize(1..=heights[from]);
+
+        heights[from] -= count;
+        heights[to] += count;
+        lines.push(format!("move {} from {} to {}", count, from + 1, to + 1));
+    }
+
+    lines
+}
+
+/// Characters from a three letter alphabet can't form a marker, so both markers lie in the planted run of
+/// 14 distinct characters at the earliest.
+fn datastream(rng: &mut Rng, length: usize) -> String {
+    let prefix_length = rng.usize(..length);
+    let mut result: String = (0..prefix_length).map(|_| rng.char('a'..='c')).collect();
+
+    let mut alphabet: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut alphabet);
+    result.extend(&alphabet[..14]);
+
+    let suffix_length = length.saturating_sub(prefix_length + 14);
+    result.extend((0..suffix_length).map(|_| rng.lowercase()));
+
+    result
+}
+
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    files: Vec<(String, usize)>,
+}
+
+/// Transcript of exploring a random directory tree. The total size lies between 40M and 70M, so that there is
+/// a directory worth deleting.
+fn terminal_output(rng: &mut Rng, dir_count: usize) -> Vec<String> {
+    let mut dirs = vec![Dir {
+        name: "/".to_string(),
+        dirs: Vec::new(),
+        files: Vec::new(),
+    }];
+    let mut names: Vec<HashSet<String>> = vec![HashSet::new()];
+
+    for id in 1..dir_count {
+        let parent = rng.usize(..id);
+        let name = unique_name(rng, &mut names[parent], false);
+        dirs.push(Dir {
+            name,
+            dirs: Vec::new(),
+            files: Vec::new(),
+        });
+        dirs[parent].dirs.push(id);
+        names.push(HashSet::new());
+    }
+
+    let mut weights = Vec::new();
+    for (id, dir) in dirs.iter_mut().enumerate() {
+        for _ in 0..rng.usize(1..=4) {
+            let name = unique_name(rng, &mut names[id], true);
+            dir.files.push((name, 0));
+            weights.push(rng.usize(1..=1000));
+        }
+    }
+
+    let total_size = rng.usize(40_000_001..70_000_000);
+    let total_weight: usize = weights.iter().sum();
+    let mut sizes = weights
+        .iter()
+        .map(|x| (x * total_size / total_weight).max(1));
+    for dir in dirs.iter_mut() {
+        for file in dir.files.iter_mut() {
+            file.1 = sizes.next().unwrap();
+        }
+    }
+    // Rounding down loses a few bytes, the root makes up for them
+    let generated: usize = dirs.iter().flat_map(|x| &x.files).map(|x| x.1).sum();
+    dirs[0].files[0].1 += total_size.saturating_sub(generated);
+
+    let mut lines = vec!["$ cd /".to_string()];
+    list_dir(rng, &dirs, 0, &mut lines);
+
+    lines
+}
+
+fn list_dir(rng: &mut Rng, dirs: &[Dir], id: usize, lines: &mut Vec<String>) {
+    let dir = &dirs[id];
+
+    let mut entries: Vec<String> = dir
+        .dirs
+        .iter()
+        .map(|&x| format!("dir {}", dirs[x].name))
+        .chain(
+            dir.files
+                .iter()
+                .map(|(name, size)| format!("{} {}", size, name)),
+        )
+        .collect();
+    rng.shuffle(&mut entries);
+    lines.push("$ ls".to_string());
+    lines.extend(entries);
+
+    for &child in dir.dirs.iter() {
+        lines.push(format!("$ cd {}", dirs[child].name));
+        list_dir(rng, dirs, child, lines);
+        lines.push("$ cd ..".to_string());
+    }
+}
+
+fn unique_name(rng: &mut Rng, taken: &mut HashSet<String>, file: bool) -> String {
+    loop {
+        let mut name: String = (0..rng.usize(1..=8)).map(|_| rng.lowercase()).collect();
+        if file && rng.bool() {
+            name.push('.');
+            name.extend((0..3).map(|_| rng.lowercase()));
+        }
+
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+fn tree_grid(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| (0..size).map(|_| rng.char('0'..='9')).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::Registry;
+    use crate::solver::Part;
+
+    #[test]
+    fn generates_solvable_inputs() {
+        let registry = Registry::new();
+        for (day, solver) in registry.iter() {
+            for (size, seed) in [(1, 1), (10, 2), (200, 3)] {
+                // Days just created with new-day don't have a generator yet
+                let Some(input) = generate(day, size, seed) else {
+                    continue;
+                };
+
+                for part in Part::ALL {
+                    let result = solver.solve(part, &mut input.as_bytes());
+                    assert!(
+                        result.is_ok(),
+                        "day {}, part {}, size {}, seed {}: {:?}",
+                        day,
+                        part,
+                        size,
+                        seed,
+                        result
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn repeats_input_for_same_seed() {
+        assert_eq!(generate(7, 20, 42), generate(7, 20, 42));
+        assert_ne!(generate(7, 20, 42), generate(7, 20, 43));
+    }
+
+    #[test]
+    fn returns_none_for_unknown_day() {
+        assert_eq!(None, generate(25, 10, 1));
+    }
+}
@@ -0,0 +1,380 @@
+use std::{
+    fmt, iter,
+    ops::{Index, IndexMut},
+};
+
+use crate::error::Error;
+
+/// Zero-based position of a cell, parse errors report it one-based as line and column.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct Position {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, column: usize) -> Self {
+        Position { row, column }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+/// Rectangular grid stored row by row.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses every character of every line with `f`. All lines have to be of the same length, errors are
+    /// reported with their line and column.
+    pub fn parse<I, S, F>(lines: I, mut f: F) -> Result<Grid<T>, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: FnMut(char) -> Result<T, Error>,
+    {
+        let mut cells = Vec::new();
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+
+        for (row, line) in lines.into_iter().enumerate() {
+            let line = line.as_ref();
+            let line_length = line.chars().count();
+            match width {
+                Some(width) if width != line_length => {
+                    return Err(Error::parse(format!(
+                        "line length mismatch, expected length {}, found length {}",
+                        width, line_length
+                    ))
+                    .at_line(row + 1));
+                }
+                _ => width = Some(line_length),
+            }
+
+            for (column, char) in line.chars().enumerate() {
+                cells.push(f(char).map_err(|e| e.at_line(row + 1).at_column(column + 1))?);
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.row < self.height && position.column < self.width
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        let position = Position::new(row, column);
+        self.contains(position)
+            .then(|| &self.cells[self.cell_index(position)])
+    }
+
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut T> {
+        let position = Position::new(row, column);
+        if self.contains(position) {
+            let index = self.cell_index(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over all cells row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (Position::new(i / self.width, i % self.width), x))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|x| &self.cells[x * self.width..(x + 1) * self.width])
+    }
+
+    /// Iterates over a column from top to bottom, empty if the column is out of bounds.
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        let height = if column < self.width { self.height } else { 0 };
+        (0..height).map(move |x| &self[Position::new(x, column)])
+    }
+
+    /// Position next to `position` in `direction`, unless it's outside of the grid.
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let (row_offset, column_offset) = direction.offset();
+        let result = Position::new(
+            position.row.checked_add_signed(row_offset)?,
+            position.column.checked_add_signed(column_offset)?,
+        );
+
+        self.contains(result).then_some(result)
+    }
+
+    /// Cells next to `position` in the given directions, skipping the ones outside of the grid.
+    pub fn neighbors<'a>(
+        &'a self,
+        position: Position,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (Position, &'a T)> + 'a {
+        directions
+            .iter()
+            .filter_map(move |&x| self.step(position, x))
+            .map(|x| (x, &self[x]))
+    }
+
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbors(position, &Direction::ORTHOGONAL)
+    }
+
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbors(position, &Direction::ALL)
+    }
+
+    /// Cells from `position` (excluded) in `direction` up to the edge of the grid.
+    pub fn ray(
+        &self,
+        position: Position,
+        direction: Direction,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        iter::successors(self.step(position, direction), move |&x| {
+            self.step(x, direction)
+        })
+        .map(|x| (x, &self[x]))
+    }
+
+    pub fn map<U, F: FnMut(Position, &T) -> U>(&self, mut f: F) -> Grid<U> {
+        Grid {
+            cells: self.iter().map(|(position, x)| f(position, x)).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn cell_index(&self, position: Position) -> usize {
+        position.row * self.width + position.column
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        assert!(self.contains(position), "{:?} is out of bounds", position);
+        &self.cells[self.cell_index(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        assert!(self.contains(position), "{:?} is out of bounds", position);
+        let index = self.cell_index(position);
+        &mut self.cells[index]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_chars(lines: &[&str]) -> Grid<char> {
+        Grid::parse(lines, Ok).unwrap()
+    }
+
+    #[test]
+    fn parses_lines() {
+        let grid = parse_chars(&["abc", "def"]);
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(Some(&'f'), grid.get(1, 2));
+        assert_eq!(None, grid.get(2, 0));
+        assert_eq!(None, grid.get(0, 3));
+    }
+
+    #[test]
+    fn parses_empty_input() {
+        let grid = parse_chars(&[]);
+
+        assert_eq!(0, grid.width());
+        assert_eq!(0, grid.height());
+        assert_eq!(0, grid.iter().count());
+    }
+
+    #[test]
+    fn returns_parse_error_with_position() {
+        let result = Grid::parse(["12", "3a"], |x| {
+            x.to_digit(10)
+                .ok_or_else(|| Error::parse(format!("invalid character '{}'", x)))
+        });
+
+        assert_eq!(
+            Err(Error::parse("invalid character 'a'")
+                .at_line(2)
+                .at_column(2)),
+            result
+        );
+    }
+
+    #[test]
+    fn returns_error_on_unequal_length() {
+        assert_eq!(
+            Err(Error::parse("line length mismatch, expected length 2, found length 1").at_line(3)),
+            Grid::parse(["ab", "cd", "e"], Ok)
+        );
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = parse_chars(&["abc", "def"]);
+
+        assert_eq!(Some(['d', 'e', 'f'].as_slice()), grid.row(1));
+        assert_eq!(None, grid.row(2));
+        assert_eq!(2, grid.rows().count());
+        assert_eq!(vec![&'b', &'e'], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(0, grid.column(3).count());
+        assert_eq!(
+            vec![(Position::new(0, 0), &'a'), (Position::new(0, 1), &'b')],
+            grid.iter().take(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn returns_neighbors_within_bounds() {
+        let grid = parse_chars(&["abc", "def", "ghi"]);
+
+        let corner: Vec<_> = grid
+            .neighbors4(Position::new(0, 0))
+            .map(|(_, x)| *x)
+            .collect();
+        assert_eq!(vec!['d', 'b'], corner);
+
+        let center: Vec<_> = grid
+            .neighbors8(Position::new(1, 1))
+            .map(|(_, x)| *x)
+            .collect();
+        assert_eq!(vec!['b', 'h', 'd', 'f', 'a', 'c', 'g', 'i'], center);
+
+        let edge: Vec<_> = grid
+            .neighbors8(Position::new(2, 1))
+            .map(|(_, x)| *x)
+            .collect();
+        assert_eq!(vec!['e', 'g', 'i', 'd', 'f'], edge);
+    }
+
+    #[test]
+    fn casts_rays_to_edge() {
+        let grid = parse_chars(&["abc", "def", "ghi"]);
+
+        let ray: Vec<_> = grid
+            .ray(Position::new(2, 2), Direction::UpLeft)
+            .map(|(_, x)| *x)
+            .collect();
+        assert_eq!(vec!['e', 'a'], ray);
+        assert_eq!(0, grid.ray(Position::new(0, 1), Direction::Up).count());
+    }
+
+    #[test]
+    fn maps_and_displays_cells() {
+        let grid = parse_chars(&["ab", "cd"]);
+        let mut mapped = grid.map(|position, x| {
+            if position.row == position.column {
+                x.to_ascii_uppercase()
+            } else {
+                *x
+            }
+        });
+        mapped[Position::new(0, 1)] = '#';
+        *mapped.get_mut(1, 0).unwrap() = '.';
+
+        assert_eq!("A#\n.D", mapped.to_string());
+        assert_eq!("..\n..", Grid::new(2, 2, '.').to_string());
+    }
+}
@@ -0,0 +1,391 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use toml::Value;
+
+use crate::answers::Answers;
+use crate::error::Error;
+use crate::registry;
+use crate::solver::{Answer, Part};
+
+lazy_static! {
+    static ref TITLE_REGEX: Regex = Regex::new(r"<h2>--- Day (\d+):").unwrap();
+    static ref ARTICLE_REGEX: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    static ref CODE_BLOCK_REGEX: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    static ref HIGHLIGHT_REGEX: Regex =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+    static ref EMPHASIS_REGEX: Regex = Regex::new(r"(?s)<em>(.*?)</em>").unwrap();
+    static ref TAG_REGEX: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref NUMBER_REGEX: Regex = Regex::new(r"^-?\d+$").unwrap();
+}
+
+/// Example of a saved puzzle page along with the answers the description gives for it.
+#[derive(PartialEq, Debug)]
+pub struct Example {
+    /// Day from the page title, if it has one.
+    pub day: Option<u32>,
+    pub input: String,
+    /// Answers of the parts described on the page, part two is only there once part one is solved.
+    pub answers: Vec<(Part, Answer)>,
+}
+
+/// Extracts the first code block of part one as the example input. The answer of each part is the last highlighted
+/// code in its description, or the last emphasized number if there's none.
+pub fn extract(html: &str) -> Result<Example, Error> {
+    let articles: Vec<&str> = ARTICLE_REGEX
+        .captures_iter(html)
+        .map(|x| x.get(1).unwrap().as_str())
+        .collect();
+    let Some(first) = articles.first() else {
+        return Err(Error::parse("no puzzle description found"));
+    };
+
+    let input = CODE_BLOCK_REGEX
+        .captures(first)
+        .map(|x| text(&x[1]))
+        .ok_or_else(|| Error::parse("no example input found"))?;
+
+    let mut answers = Vec::new();
+    for (part, article) in Part::ALL.into_iter().zip(&articles) {
+        let answer = answer(article)
+            .ok_or_else(|| Error::parse(format!("no example answer found for part {}", part)))?;
+        answers.push((part, answer));
+    }
+
+    Ok(Example {
+        day: TITLE_REGEX.captures(html).and_then(|x| x[1].parse().ok()),
+        input,
+        answers,
+    })
+}
+
+fn answer(article: &str) -> Option<Answer> {
+    let highlighted = HIGHLIGHT_REGEX
+        .captures_iter(article)
+        .filter_map(|x| x.get(1).or(x.get(2)))
+        .map(|x| text(x.as_str()))
+        .last();
+    let emphasized = || {
+        EMPHASIS_REGEX
+            .captures_iter(article)
+            .map(|x| text(&x[1]))
+            .filter(|x| NUMBER_REGEX.is_match(x))
+            .last()
+    };
+
+    highlighted.or_else(emphasized).map(|x| match x.parse() {
+        Ok(number) => Answer::Number(number),
+        Err(_) => Answer::Text(x),
+    })
+}
+
+/// Content of an HTML fragment as plain text.
+fn text(html: &str) -> String {
+    TAG_REGEX
+        .replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Writes the example input of `day` and its answers to the example fixtures under `root`, replacing the ones
+/// already there. Returns the paths of the modified files, relative to `root`.
+pub fn save(root: &Path, day: u32, example: &Example) -> Result<Vec<PathBuf>, Error> {
+    let input_path = PathBuf::from(registry::example_input_path(day));
+    let answers_path = PathBuf::from(registry::EXAMPLE_ANSWERS_PATH);
+
+    let manifest = fs::read_to_string(root.join(&answers_path)).unwrap_or_default();
+    let manifest = set_answers(&manifest, day, &example.answers)?;
+
+    if let Some(parent) = root.join(&input_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(root.join(&input_path), &example.input)?;
+    fs::write(root.join(&answers_path), manifest)?;
+
+    Ok(vec![input_path, answers_path])
+}
+
+/// Sets the answers of `day` in the manifest, keeping the other days, comments and the answers of parts not given.
+/// Answers already in the manifest are replaced where they are, along with any comment after them. New ones follow
+/// the last answer of their section or its header if it has none.
+/// Days without a section get one in order of their number.
+pub fn set_answers(manifest: &str, day: u32, answers: &[(Part, Answer)]) -> Result<String, Error> {
+    let existing = Answers::parse(manifest)?;
+    let mut values = Vec::new();
+    for part in Part::ALL {
+        let answer = answers
+            .iter()
+            .find(|(x, _)| *x == part)
+            .map(|(_, answer)| answer)
+            .or(existing.get(day, part));
+        let value = match answer {
+            Some(Answer::Number(number)) => Value::Integer(*number),
+            Some(Answer::Text(text)) => Value::String(text.clone()),
+            None => continue,
+        };
+        values.push((part, format!("part{} = {}", part.number(), value)));
+    }
+
+    let lines: Vec<&str> = manifest.lines().collect();
+    let section_day = |line: &str| {
+        line.trim()
+            .strip_prefix("[day")
+            .and_then(|x| x.strip_suffix(']'))
+            .and_then(|x| x.parse::<u32>().ok())
+    };
+    let start = lines
+        .iter()
+        .position(|x| section_day(x).is_some_and(|x| x >= day));
+
+    let mut result: Vec<String> = Vec::new();
+    let new_section = || {
+        let mut section = vec![format!("[day{}]", day)];
+        section.extend(values.iter().map(|(_, line)| line.clone()));
+        section
+    };
+    match start {
+        Some(start) if section_day(lines[start]) == Some(day) => {
+            let end = lines[start + 1..]
+                .iter()
+                .position(|x| x.trim().starts_with('['))
+                .map_or(lines.len(), |x| start + 1 + x);
+            let mut section: Vec<String> = Vec::new();
+            let mut written = Vec::new();
+            let mut last_answer = 0;
+            for line in &lines[start..end] {
+                match answer_part(line) {
+                    Some(part) => {
+                        if let Some((_, value)) = values.iter().find(|(x, _)| *x == part) {
+                            section.push(value.clone() + inline_comment(line));
+                            written.push(part);
+                        }
+                        last_answer = section.len();
+                    }
+                    None => section.push(line.to_string()),
+                }
+            }
+            let missing = values
+                .iter()
+                .filter(|(x, _)| !written.contains(x))
+                .map(|(_, value)| value.clone());
+            let last_answer = last_answer.max(1);
+            section.splice(last_answer..last_answer, missing);
+
+            result.extend(lines[..start].iter().map(|x| x.to_string()));
+            result.extend(section);
+            result.extend(lines[end..].iter().map(|x| x.to_string()));
+        }
+        Some(start) => {
+            result.extend(lines[..start].iter().map(|x| x.to_string()));
+            result.extend(new_section());
+            result.push(String::new());
+            result.extend(lines[start..].iter().map(|x| x.to_string()));
+        }
+        None => {
+            result.extend(lines.iter().map(|x| x.to_string()));
+            if lines.last().is_some_and(|x| !x.trim().is_empty()) {
+                result.push(String::new());
+            }
+            result.extend(new_section());
+        }
+    }
+
+    Ok(result.join("\n").trim_end().to_string() + "\n")
+}
+
+/// Comment at the end of a line, including the blanks before it, or an empty string if there's none.
+fn inline_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), _) if escaped => escaped = false,
+            (Some('"'), '\\') => escaped = true,
+            (Some(x), c) if x == c => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[line[..index].trim_end().len()..],
+            _ => {}
+        }
+    }
+
+    ""
+}
+
+/// Part whose answer is set on the line, if any.
+fn answer_part(line: &str) -> Option<Part> {
+    let (key, _) = line.split_once('=')?;
+    Part::ALL
+        .into_iter()
+        .find(|x| key.trim() == format!("part{}", x.number()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 5: Supply Stacks ---</h2>
+<p>For example:</p>
+<pre><code>    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+</code></pre>
+<p>The crates end up as <code>&lt;C&gt;</code>, so you should give the Elves <code><em>CMZ</em></code>.</p>
+<p><em>After the rearrangement procedure completes, what crate ends up on top of each stack?</em></p>
+</article>
+<p>Your puzzle answer was <code>ABC</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In this example, the CrateMover 9001 leaves <code><em>MCD</em></code> on top.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn extracts_example_and_answers() {
+        assert_eq!(
+            Ok(Example {
+                day: Some(5),
+                input: "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\nmove 1 from 2 to 1\n"
+                    .to_string(),
+                answers: vec![
+                    (Part::One, Answer::Text("CMZ".to_string())),
+                    (Part::Two, Answer::Text("MCD".to_string())),
+                ],
+            }),
+            extract(PAGE)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_emphasized_numbers() {
+        let page = "<article><pre><code>1000\n&amp;\n</code></pre>\
+                    <p>The <em>fourth</em> Elf carries <em>24000</em> Calories.</p>\
+                    <p><em>How many total Calories is that Elf carrying?</em></p></article>";
+
+        let example = extract(page).unwrap();
+        assert_eq!(None, example.day);
+        assert_eq!("1000\n&\n", example.input);
+        assert_eq!(vec![(Part::One, Answer::Number(24000))], example.answers);
+    }
+
+    #[test]
+    fn handles_pages_without_example() {
+        assert_eq!(
+            Err(Error::parse("no puzzle description found")),
+            extract("<html></html>")
+        );
+        assert_eq!(
+            Err(Error::parse("no example input found")),
+            extract("<article><p>No example</p></article>")
+        );
+        assert_eq!(
+            Err(Error::parse("no example answer found for part 1")),
+            extract("<article><pre><code>1\n</code></pre></article>")
+        );
+    }
+
+    #[test]
+    fn sets_answers_in_manifest() {
+        let manifest = "# Answers\n\n[day1]\npart1 = 1\npart2 = 2\n\n[day5]\npart1 = \"A\"\n";
+
+        assert_eq!(
+            "# Answers\n\n[day1]\npart1 = 1\npart2 = 2\n\n[day5]\npart1 = \"CMZ\"\n",
+            set_answers(manifest, 5, &[(Part::One, "CMZ".to_string().into())]).unwrap()
+        );
+        assert_eq!(
+            "# Answers\n\n[day1]\npart1 = 3\npart2 = 2\n\n[day5]\npart1 = \"A\"\n",
+            set_answers(manifest, 1, &[(Part::One, Answer::Number(3))]).unwrap()
+        );
+        assert_eq!(
+            "# Answers\n\n[day1]\npart1 = 1\npart2 = 2\n\n[day3]\npart1 = 7\n\n\
+             [day5]\npart1 = \"A\"\n",
+            set_answers(manifest, 3, &[(Part::One, Answer::Number(7))]).unwrap()
+        );
+        assert_eq!(
+            "# Answers\n\n[day1]\npart1 = 1\npart2 = 2\n\n[day5]\npart1 = \"A\"\n\n\
+             [day9]\npart2 = 9\n",
+            set_answers(manifest, 9, &[(Part::Two, Answer::Number(9))]).unwrap()
+        );
+        assert_eq!(
+            "[day2]\npart1 = 15\n",
+            set_answers("", 2, &[(Part::One, Answer::Number(15))]).unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_comments_of_replaced_section() {
+        let manifest =
+            "# Answers\n\n[day5]\n# From the first example\npart1 = \"A\" # top crates\n\n\
+             # Day 6 has several examples\n[day6]\npart1 = 7\n";
+
+        assert_eq!(
+            "# Answers\n\n[day5]\n# From the first example\npart1 = \"CMZ\" # top crates\npart2 = \"MCD\"\n\n\
+             # Day 6 has several examples\n[day6]\npart1 = 7\n",
+            set_answers(
+                manifest,
+                5,
+                &[
+                    (Part::One, "CMZ".to_string().into()),
+                    (Part::Two, "MCD".to_string().into())
+                ]
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "[day6]\npart2 = 5\n# Only part one so far\n",
+            set_answers(
+                "[day6]\n# Only part one so far\n",
+                6,
+                &[(Part::Two, Answer::Number(5))]
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn finds_inline_comments() {
+        assert_eq!("  # top", inline_comment("part1 = \"A\"  # top"));
+        assert_eq!("", inline_comment("part1 = \"A#B\""));
+        assert_eq!(" # c", inline_comment("part1 = 'A#B' # c"));
+        assert_eq!(" # c", inline_comment("part1 = \"A\\\"#\" # c"));
+        assert_eq!("", inline_comment("part1 = 5"));
+    }
+
+    #[test]
+    fn saves_fixtures() {
+        let root = env::temp_dir().join(format!("aoc2022-import-{}", std::process::id()));
+        let example = extract(PAGE).unwrap();
+
+        let paths = save(&root, 5, &example).unwrap();
+
+        assert_eq!(
+            vec![
+                PathBuf::from("examples/day5.txt"),
+                PathBuf::from("examples/answers.toml")
+            ],
+            paths
+        );
+        assert_eq!(
+            example.input,
+            fs::read_to_string(root.join("examples/day5.txt")).unwrap()
+        );
+        let answers = Answers::load(root.join("examples/answers.toml").to_str().unwrap()).unwrap();
+        assert_eq!(
+            Some(&Answer::Text("MCD".to_string())),
+            answers.get(5, Part::Two)
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
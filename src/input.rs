@@ -0,0 +1,161 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+};
+
+use crate::error::Error;
+
+pub const STDIN_PATH: &str = "-";
+
+/// Reads the whole input up front, so that stdin (passed as `-`) can be solved for both parts.
+pub fn read_input(path: &str) -> Result<String, Error> {
+    if path == STDIN_PATH {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        Ok(fs::read_to_string(path)?)
+    }
+}
+
+pub fn open(path: &str) -> Result<BufReader<File>, Error> {
+    Ok(BufReader::new(File::open(path)?))
+}
+
+/// Reads all lines, dropping trailing whitespace including the `\r` of Windows line endings.
+pub fn read_lines<R: BufRead>(reader: R) -> Result<Vec<String>, Error> {
+    reader
+        .lines()
+        .map(|x| {
+            x.map(|line| line.trim_end().to_string())
+                .map_err(Error::from)
+        })
+        .collect()
+}
+
+/// Reads and parses every line with `f`, attaching the line number to parse errors.
+pub fn parse_lines<R, T, F>(reader: R, f: F) -> Result<Vec<T>, Error>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<T, Error>,
+{
+    Section::new(1, read_lines(reader)?).parse_lines(f)
+}
+
+/// Run of consecutive non-blank lines.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Section {
+    /// Line number of the first line within the whole input
+    pub start: usize,
+    pub lines: Vec<String>,
+}
+
+impl Section {
+    pub fn new(start: usize, lines: Vec<String>) -> Self {
+        Section { start, lines }
+    }
+
+    /// Line number of the line at `index` within the whole input.
+    pub fn line_number(&self, index: usize) -> usize {
+        self.start + index
+    }
+
+    /// Parses every line with `f`, attaching the line number to parse errors.
+    pub fn parse_lines<T, F>(&self, mut f: F) -> Result<Vec<T>, Error>
+    where
+        F: FnMut(&str) -> Result<T, Error>,
+    {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| f(line).map_err(|e| e.at_line(self.line_number(index))))
+            .collect()
+    }
+}
+
+/// Reads the input as sections separated by blank lines.
+pub fn read_sections<R: BufRead>(reader: R) -> Result<Vec<Section>, Error> {
+    Ok(split_sections(read_lines(reader)?))
+}
+
+/// Splits lines into sections separated by one or more blank lines. Leading and trailing blank lines are ignored.
+pub fn split_sections(lines: Vec<String>) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut current: Option<Section> = None;
+    for (index, line) in lines.into_iter().enumerate() {
+        if line.trim().is_empty() {
+            sections.extend(current.take());
+        } else {
+            current
+                .get_or_insert_with(|| Section::new(index + 1, Vec::new()))
+                .lines
+                .push(line);
+        }
+    }
+    sections.extend(current);
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_error_for_missing_file() {
+        assert_eq!(
+            Err(Error::Io(io::ErrorKind::NotFound.into())),
+            read_input("inputs/missing.txt")
+        );
+    }
+
+    #[test]
+    fn reads_trimmed_lines() {
+        assert_eq!(
+            Ok(vec!["a".to_string(), "  b".to_string(), "".to_string()]),
+            read_lines("a \r\n  b\n\t\n".as_bytes())
+        );
+    }
+
+    #[test]
+    fn parses_lines_with_line_numbers() {
+        let parse = |x: &str| {
+            x.parse::<i32>()
+                .map_err(|_| Error::parse(format!("invalid number '{}'", x)))
+        };
+
+        assert_eq!(Ok(vec![1, 2]), parse_lines("1\n2\n".as_bytes(), parse));
+        assert_eq!(
+            Err(Error::parse("invalid number 'x'").at_line(2)),
+            parse_lines("1\nx\n".as_bytes(), parse)
+        );
+    }
+
+    #[test]
+    fn splits_sections_on_blank_lines() {
+        let sections = read_sections("\na\nb\n\n  \nc\n\n".as_bytes()).unwrap();
+
+        assert_eq!(
+            vec![
+                Section::new(2, vec!["a".to_string(), "b".to_string()]),
+                Section::new(6, vec!["c".to_string()]),
+            ],
+            sections
+        );
+        assert_eq!(7, sections[1].line_number(1));
+        assert!(read_sections("\n\n".as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parses_section_lines_with_line_numbers() {
+        let section = Section::new(4, vec!["ok".to_string(), "bad".to_string()]);
+
+        assert_eq!(
+            Err(Error::parse("bad line").at_line(5)),
+            section.parse_lines(|x| match x {
+                "ok" => Ok(()),
+                _ => Err(Error::parse("bad line")),
+            })
+        );
+    }
+}
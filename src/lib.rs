@@ -0,0 +1,36 @@
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod client;
+pub mod config;
+pub mod day1_part1;
+pub mod day1_part2;
+pub mod day2_part1;
+pub mod day2_part2;
+pub mod day3_part1;
+pub mod day3_part2;
+pub mod day4_part1;
+pub mod day4_part2;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod differential;
+pub mod error;
+pub mod fuzz;
+pub mod generate;
+pub mod grid;
+pub mod import;
+pub mod input;
+pub mod logging;
+pub mod pool;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod server;
+pub mod solver;
+pub mod timing;
+pub mod trace;
+pub mod verify;
+pub mod watch;
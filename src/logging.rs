@@ -0,0 +1,46 @@
+use std::io::{self, IsTerminal};
+
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+use crate::error::Error;
+
+pub const LOG_VARIABLE: &str = "AOC_LOG";
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Format {
+    Text,
+    /// One JSON object per event, with the spans it happened in.
+    Json,
+}
+
+/// Writes the events of spans and levels selected by `filter` to stderr. The filter takes the form of `RUST_LOG`,
+/// e.g. `debug` or `[day{day=7}]=trace`. Nothing is logged unless this is called.
+pub fn init(filter: &str, format: Format) -> Result<(), Error> {
+    let filter = EnvFilter::try_new(filter)
+        .map_err(|e| Error::parse(format!("invalid log filter '{}': {}", filter, e)))?;
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_span_events(FmtSpan::CLOSE);
+
+    match format {
+        Format::Text => builder.init(),
+        Format::Json => builder.json().init(),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_filter() {
+        assert!(matches!(
+            init("day7=loud", Format::Text),
+            Err(Error::Parse { .. })
+        ));
+    }
+}
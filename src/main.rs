@@ -1,28 +1,535 @@
-mod day1_part1;
-mod day1_part2;
-mod day2_part1;
-mod day2_part2;
-mod day3_part1;
-mod day3_part2;
-mod day4_part1;
-mod day4_part2;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-
-fn main() {
-    day1_part1::run("inputs/day1.txt");
-    day1_part2::run("inputs/day1.txt");
-    day2_part1::run("inputs/day2.txt");
-    day2_part2::run("inputs/day2.txt");
-    day3_part1::run("inputs/day3.txt");
-    day3_part2::run("inputs/day3.txt");
-    day4_part1::run("inputs/day4.txt");
-    day4_part2::run("inputs/day4.txt");
-    day5::run_part1("inputs/day5.txt");
-    day5::run_part2("inputs/day5.txt");
-    day6::run("inputs/day6.txt");
-    day7::run("inputs/day7.txt");
-    day8::run("inputs/day8.txt");
+mod cli;
+
+use std::{
+    collections::BTreeMap, fs, net::TcpListener, path::Path, process::ExitCode, thread,
+    time::Duration,
+};
+
+use clap::Parser;
+
+use aoc2022::answers::Answers;
+use aoc2022::bench::{self, Benchmark};
+use aoc2022::cache::{Cache, DEFAULT_CACHE_PATH};
+use aoc2022::client;
+use aoc2022::config::Config;
+use aoc2022::differential;
+use aoc2022::error::Error;
+use aoc2022::generate;
+use aoc2022::import;
+use aoc2022::input;
+use aoc2022::pool;
+use aoc2022::registry::{self, Registry};
+use aoc2022::report::Report;
+use aoc2022::runner::{self, Job, RunResult};
+use aoc2022::scaffold;
+use aoc2022::server;
+use aoc2022::solver::Part;
+use aoc2022::verify::{self, Summary, Verification};
+use aoc2022::watch::{Diff, Watcher};
+use cli::{
+    BenchArgs, Cli, Command, DifferentialArgs, FetchArgs, Format, GenerateArgs, ImportArgs,
+    NewDayArgs, ReportArgs, ReportFormat, RunArgs, ServeArgs, SubmitArgs, VerifyArgs, WatchArgs,
+};
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Err(error) = cli.log.init() {
+        eprintln!("{}", error);
+        return ExitCode::FAILURE;
+    }
+    let config = match cli.config.load() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}: {}", cli.config.path(), error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match cli.command {
+        Command::Run(args) => run(args, &config),
+        Command::Verify(args) => verify(args, &config),
+        Command::Bench(args) => bench(args, &config),
+        Command::Watch(args) => watch(args, &config),
+        Command::NewDay(args) => new_day(args),
+        Command::Generate(args) => generate(args),
+        Command::Differential(args) => differential(args, &config),
+        Command::Serve(args) => serve(args, &config),
+        Command::Fetch(args) => fetch(args, &config),
+        Command::Submit(args) => submit(args, &config),
+        Command::Import(args) => import(args),
+        Command::Report(args) => report(args, &config),
+    }
+}
+
+fn run(args: RunArgs, config: &Config) -> ExitCode {
+    let registry = Registry::with_config(config);
+
+    let mut jobs = Vec::new();
+    for day in selected_days(&registry, args.day) {
+        let Some(solver) = registry.get(day) else {
+            eprintln!("day {} is not implemented", day);
+            return ExitCode::FAILURE;
+        };
+
+        let path = args.input.clone().unwrap_or_else(|| config.input_path(day));
+        jobs.push(Job { day, solver, path });
+    }
+
+    let cache = (!args.no_cache).then(|| {
+        Cache::load(DEFAULT_CACHE_PATH).unwrap_or_else(|error| {
+            eprintln!(
+                "{}: {}, starting with an empty cache",
+                DEFAULT_CACHE_PATH, error
+            );
+            Cache::new(DEFAULT_CACHE_PATH)
+        })
+    });
+    let results = runner::run_days(
+        &jobs,
+        &args.parts(),
+        args.jobs as usize,
+        args.explain,
+        cache.as_ref(),
+    );
+    if let Some(Err(error)) = cache.map(|x| x.save()) {
+        eprintln!("{}: {}", DEFAULT_CACHE_PATH, error);
+    }
+
+    match args.format {
+        Format::Text => results.iter().for_each(print_result),
+        Format::Json => println!("{}", runner::to_json(&results)),
+    }
+
+    if results.iter().all(|x| x.result.is_ok()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn print_result(result: &RunResult) {
+    match &result.result {
+        Ok(answer) if result.cached => println!(
+            "Day {}, part {}: {} (cached)",
+            result.day, result.part, answer
+        ),
+        Ok(answer) => println!("Day {}, part {}: {}", result.day, result.part, answer),
+        Err(error) => eprintln!(
+            "Day {}, part {}: {}: {}",
+            result.day, result.part, result.input, error
+        ),
+    }
+    for step in &result.explanation {
+        println!("  {}", step);
+    }
+}
+
+fn verify(args: VerifyArgs, config: &Config) -> ExitCode {
+    // Example answers come from the puzzle values, the config only applies to personal inputs
+    let registry = if args.examples {
+        Registry::new()
+    } else {
+        Registry::with_config(config)
+    };
+    let answers_path = args.answers_path();
+    let answers = match Answers::load(&answers_path) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("{}: {}", answers_path, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut days = Vec::new();
+    for day in selected_days(&registry, args.day) {
+        let Some(solver) = registry.get(day) else {
+            eprintln!("day {} is not implemented", day);
+            return ExitCode::FAILURE;
+        };
+        days.push((day, solver));
+    }
+
+    let verifications: Vec<Verification> =
+        pool::map(&days, args.jobs as usize, |&(day, solver)| {
+            verify::verify_day(day, solver, &args.input_path(config, day), &answers)
+        })
+        .into_iter()
+        .flatten()
+        .collect();
+    for verification in &verifications {
+        println!(
+            "Day {}, part {}: {}",
+            verification.day, verification.part, verification.outcome
+        );
+    }
+
+    println!("{}", Summary::new(&verifications));
+    if verifications.iter().all(|x| x.outcome.is_success()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn bench(args: BenchArgs, config: &Config) -> ExitCode {
+    let registry = Registry::with_config(config);
+    let runs = args.runs as usize;
+
+    let mut failed = false;
+    let mut benchmarks = Vec::new();
+    println!("{}", Benchmark::header());
+    for day in selected_days(&registry, args.day) {
+        let Some(solver) = registry.get(day) else {
+            eprintln!("day {} is not implemented", day);
+            return ExitCode::FAILURE;
+        };
+
+        let path = config.input_path(day);
+        let input = match input::read_input(&path) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("Day {}: {}: {}", day, path, error);
+                failed = true;
+                continue;
+            }
+        };
+
+        for part in args.parts() {
+            match Benchmark::run(day, part, solver, &input, runs) {
+                Ok(benchmark) => {
+                    println!("{}", benchmark);
+                    benchmarks.push(benchmark);
+                }
+                Err(error) => {
+                    eprintln!("Day {}, part {}: {}: {}", day, part, path, error);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    if let Some(output) = args.output {
+        if let Err(error) = fs::write(&output, bench::to_json(&benchmarks, runs)) {
+            eprintln!("{}: {}", output, error);
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn watch(args: WatchArgs, config: &Config) -> ExitCode {
+    let registry = Registry::with_config(config);
+    let Some(solver) = registry.get(args.day) else {
+        eprintln!("day {} is not implemented", args.day);
+        return ExitCode::FAILURE;
+    };
+
+    let paths = args.paths(config);
+    let mut watcher = Watcher::new(&paths);
+    let mut previous = BTreeMap::new();
+    let mut changed = paths;
+    loop {
+        for path in changed {
+            for result in runner::run_day(args.day, solver, &path, &args.parts(), false, None) {
+                let key = (path.clone(), result.part);
+                let diff = Diff::new(previous.remove(&key), result.result);
+                println!(
+                    "Day {}, part {}, {}: {}",
+                    result.day, result.part, path, diff
+                );
+                previous.insert(key, diff.current().clone());
+            }
+        }
+
+        thread::sleep(Duration::from_millis(args.interval));
+        changed = watcher.poll();
+    }
+}
+
+fn new_day(args: NewDayArgs) -> ExitCode {
+    match scaffold::create(Path::new("."), args.day) {
+        Ok(paths) => {
+            for path in paths {
+                println!("{}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Day {}: {}", args.day, error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn generate(args: GenerateArgs) -> ExitCode {
+    let seed = args.seed.unwrap_or_else(|| {
+        let seed = fastrand::u64(..);
+        eprintln!("Seed: {}", seed);
+        seed
+    });
+
+    let Some(input) = generate::generate(args.day, args.size as usize, seed) else {
+        eprintln!("day {} has no input generator", args.day);
+        return ExitCode::FAILURE;
+    };
+
+    match args.output {
+        Some(output) => {
+            if let Err(error) = fs::write(&output, input) {
+                eprintln!("{}: {}", output, error);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", input),
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn differential(args: DifferentialArgs, config: &Config) -> ExitCode {
+    // Variants use the puzzle values of the constants, so the solvers have to as well
+    let registry = Registry::new();
+    let seed = args.seed.unwrap_or_else(|| {
+        let seed = fastrand::u64(..);
+        eprintln!("Seed: {}", seed);
+        seed
+    });
+
+    let mut failed = false;
+    let mut checked = 0;
+    for day in selected_days(&registry, args.day) {
+        let Some(solver) = registry.get(day) else {
+            eprintln!("day {} is not implemented", day);
+            return ExitCode::FAILURE;
+        };
+
+        let inputs =
+            differential::inputs(config, day, args.runs as usize, args.size as usize, seed);
+        for part in Part::ALL {
+            for variant in registry.variants(day, part) {
+                checked += 1;
+                match differential::check_variant(solver, variant, &inputs) {
+                    Some(disagreement) => {
+                        println!("{}", disagreement);
+                        failed = true;
+                    }
+                    None => println!(
+                        "Day {}, part {}, {}: agrees on {} inputs",
+                        day,
+                        part,
+                        variant.name,
+                        inputs.len()
+                    ),
+                }
+            }
+        }
+    }
+
+    if checked == 0 {
+        eprintln!("no variants registered");
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn serve(args: ServeArgs, config: &Config) -> ExitCode {
+    let listener = match TcpListener::bind(&args.address) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("{}: {}", args.address, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Ok(address) = listener.local_addr() {
+        eprintln!("Listening on http://{}", address);
+    }
+    server::serve(listener, &Registry::with_config(config), args.jobs as usize);
+
+    ExitCode::SUCCESS
+}
+
+fn fetch(args: FetchArgs, config: &Config) -> ExitCode {
+    let path = config.input_path(args.day);
+    if !args.force && client::is_fetched(Path::new(&path)) {
+        println!("Day {}: {} is already fetched", args.day, path);
+        return ExitCode::SUCCESS;
+    }
+
+    let client = match args.endpoint.client() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    match client::fetch_to(&client, args.day, Path::new(&path)) {
+        Ok(()) => {
+            println!("Day {}: fetched {}", args.day, path);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Day {}: {}", args.day, error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(args: SubmitArgs, config: &Config) -> ExitCode {
+    let client = match args.endpoint.client() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            // The official answer needs the puzzle values, not what-if overrides of the config
+            let registry = Registry::new();
+            let Some(solver) = registry.get(args.day) else {
+                eprintln!("day {} is not implemented", args.day);
+                return ExitCode::FAILURE;
+            };
+
+            let path = config.input_path(args.day);
+            let results = runner::run_day(args.day, solver, &path, &[args.part], false, None);
+            match &results[0].result {
+                Ok(answer) => answer.to_string(),
+                Err(error) => {
+                    eprintln!("Day {}, part {}: {}: {}", args.day, args.part, path, error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    };
+
+    println!(
+        "Day {}, part {}: submitting {}",
+        args.day, args.part, answer
+    );
+    match client.submit(args.day, args.part, &answer) {
+        Ok(submission) => {
+            println!("Day {}, part {}: {}", args.day, args.part, submission);
+            if submission.is_correct() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(error) => {
+            eprintln!("Day {}, part {}: {}", args.day, args.part, error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn import(args: ImportArgs) -> ExitCode {
+    let example = match fs::read_to_string(&args.page)
+        .map_err(Error::from)
+        .and_then(|x| import::extract(&x))
+    {
+        Ok(example) => example,
+        Err(error) => {
+            eprintln!("{}: {}", args.page, error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(day) = args.day.or(example.day) else {
+        eprintln!("{}: no day in the page title, pass --day", args.page);
+        return ExitCode::FAILURE;
+    };
+
+    match import::save(Path::new("."), day, &example) {
+        Ok(paths) => paths.iter().for_each(|x| println!("{}", x.display())),
+        Err(error) => {
+            eprintln!("Day {}: {}", day, error);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    // Checks the solver against the new fixtures right away, the example tests run the same check. The answers
+    // are for the puzzle values of the constants, so the configured ones are ignored
+    let registry = Registry::new();
+    let answers = Answers::load(registry::EXAMPLE_ANSWERS_PATH);
+    if let (Some(solver), Ok(answers)) = (registry.get(day), answers) {
+        let path = registry::example_input_path(day);
+        for verification in verify::verify_day(day, solver, &path, &answers) {
+            println!(
+                "Day {}, part {}: {}",
+                verification.day, verification.part, verification.outcome
+            );
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn report(args: ReportArgs, config: &Config) -> ExitCode {
+    // Example answers come from the puzzle values, the config only applies to personal inputs
+    let registry = if args.examples {
+        Registry::new()
+    } else {
+        Registry::with_config(config)
+    };
+
+    let mut jobs = Vec::new();
+    for day in selected_days(&registry, args.day) {
+        let Some(solver) = registry.get(day) else {
+            eprintln!("day {} is not implemented", day);
+            return ExitCode::FAILURE;
+        };
+        jobs.push(Job {
+            day,
+            solver,
+            path: args.input_path(config, day),
+        });
+    }
+
+    // A report without expected answers is still useful, every part is just unverified
+    let answers_path = args.answers_path();
+    let answers = Answers::load(&answers_path).unwrap_or_else(|error| {
+        eprintln!("{}: {}, answers are unverified", answers_path, error);
+        Answers::default()
+    });
+
+    let results = runner::run_days(&jobs, &Part::ALL, args.jobs as usize, false, None);
+    let report = Report::new(results, &answers);
+    let content = match args.format {
+        ReportFormat::Markdown => report.to_markdown(),
+        ReportFormat::Html => report.to_html(),
+    };
+
+    match args.output {
+        Some(output) => {
+            if let Err(error) = fs::write(&output, content) {
+                eprintln!("{}: {}", output, error);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", content),
+    }
+
+    if report.entries.iter().all(|x| x.outcome.is_success()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn selected_days(registry: &Registry, day: Option<u32>) -> Vec<u32> {
+    match day {
+        Some(day) => vec![day],
+        None => registry.iter().map(|(day, _)| day).collect(),
+    }
 }
@@ -0,0 +1,80 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// Applies `f` to every item on up to `workers` threads and returns the results in the order of `items`.
+/// Workers pick the next unprocessed item as soon as they are done, so slow items don't hold up the rest.
+pub fn map<T, R, F>(items: &[T], workers: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+
+                let result = f(item);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|x| x.unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn keeps_order_of_items() {
+        let items: Vec<u64> = (0..20).collect();
+
+        // Earlier items take longer, so they finish last
+        let results = map(&items, 4, |&x| {
+            thread::sleep(Duration::from_millis(20 - x));
+            x * 2
+        });
+
+        assert_eq!(items.iter().map(|x| x * 2).collect::<Vec<_>>(), results);
+    }
+
+    #[test]
+    fn bounds_number_of_threads() {
+        let threads = Mutex::new(HashSet::new());
+        let items: Vec<u32> = (0..50).collect();
+
+        map(&items, 3, |_| {
+            threads.lock().unwrap().insert(thread::current().id());
+            thread::sleep(Duration::from_millis(1));
+        });
+
+        assert!(threads.into_inner().unwrap().len() <= 3);
+    }
+
+    #[test]
+    fn handles_edge_cases() {
+        assert_eq!(Vec::<u32>::new(), map(&[], 4, |x: &u32| *x));
+        assert_eq!(vec![1, 2], map(&[1, 2], 0, |x| *x));
+        assert_eq!(vec![1, 2], map(&[1, 2], 10, |x| *x));
+    }
+}
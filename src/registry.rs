@@ -0,0 +1,269 @@
+use std::{collections::BTreeMap, io::BufRead};
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::solver::{Answer, Part, Solver, Variant};
+use crate::{
+    day1_part1, day1_part2, day2_part1, day2_part2, day3_part1, day3_part2, day4_part1, day4_part2,
+    day5, day6, day7, day8,
+};
+
+pub struct Registry {
+    solvers: BTreeMap<u32, Box<dyn Solver>>,
+    variants: Vec<Variant>,
+}
+
+impl Registry {
+    /// Solvers with the puzzle values of their constants.
+    pub fn new() -> Self {
+        Registry::with_config(&Config::default())
+    }
+
+    /// Solvers with the constants of `config`. Variants always use the puzzle values.
+    pub fn with_config(config: &Config) -> Self {
+        let mut solvers: BTreeMap<u32, Box<dyn Solver>> = BTreeMap::new();
+        solvers.insert(1, Box::new(Day1(config.day1)));
+        solvers.insert(2, Box::new(Day2));
+        solvers.insert(3, Box::new(Day3));
+        solvers.insert(4, Box::new(Day4));
+        solvers.insert(5, Box::new(Day5));
+        solvers.insert(6, Box::new(Day6(config.day6)));
+        solvers.insert(7, Box::new(Day7(config.day7)));
+        solvers.insert(8, Box::new(Day8));
+
+        let variants = vec![
+            Variant {
+                day: 3,
+                part: Part::One,
+                name: "bitset",
+                solve: |x| day3_part1::solve_bitset(x).map(Answer::from),
+            },
+            Variant {
+                day: 3,
+                part: Part::Two,
+                name: "bitset",
+                solve: |x| day3_part2::solve_bitset(x).map(Answer::from),
+            },
+            Variant {
+                day: 6,
+                part: Part::One,
+                name: "linear",
+                solve: |x| day6::solve_part1_linear(x).map(Answer::from),
+            },
+            Variant {
+                day: 6,
+                part: Part::Two,
+                name: "linear",
+                solve: |x| day6::solve_part2_linear(x).map(Answer::from),
+            },
+        ];
+
+        Registry { solvers, variants }
+    }
+
+    pub fn get(&self, day: u32) -> Option<&dyn Solver> {
+        self.solvers.get(&day).map(|x| x.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &dyn Solver)> {
+        self.solvers.iter().map(|(&day, x)| (day, x.as_ref()))
+    }
+
+    /// Alternative implementations of a part, checked against the day's solver by differential tests.
+    pub fn variants(&self, day: u32, part: Part) -> impl Iterator<Item = &Variant> {
+        self.variants
+            .iter()
+            .filter(move |x| x.day == day && x.part == part)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Registry::new()
+    }
+}
+
+pub const DEFAULT_ANSWERS_PATH: &str = "inputs/answers.toml";
+pub const EXAMPLE_ANSWERS_PATH: &str = "examples/answers.toml";
+
+pub fn default_input_path(day: u32) -> String {
+    format!("inputs/day{}.txt", day)
+}
+
+/// Path of the example input published in the puzzle description, checked in unlike personal inputs.
+pub fn example_input_path(day: u32) -> String {
+    format!("examples/day{}.txt", day)
+}
+
+struct Day1(day1_part2::Params);
+
+impl Solver for Day1 {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day1_part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day1_part2::solve(input, &self.0).map(Answer::from)
+    }
+
+    fn version(&self) -> String {
+        format!("1 {:?}", self.0)
+    }
+}
+
+struct Day2;
+
+impl Solver for Day2 {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day2_part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day2_part2::solve(input).map(Answer::from)
+    }
+}
+
+struct Day3;
+
+impl Solver for Day3 {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day3_part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day3_part2::solve(input).map(Answer::from)
+    }
+}
+
+struct Day4;
+
+impl Solver for Day4 {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day4_part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day4_part2::solve(input).map(Answer::from)
+    }
+}
+
+struct Day5;
+
+impl Solver for Day5 {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day5::solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day5::solve_part2(input).map(Answer::from)
+    }
+}
+
+struct Day6(day6::Params);
+
+impl Solver for Day6 {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day6::solve_part1(input, &self.0).map(Answer::from)
+    }
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day6::solve_part2(input, &self.0).map(Answer::from)
+    }
+
+    fn version(&self) -> String {
+        format!("1 {:?}", self.0)
+    }
+}
+
+struct Day7(day7::Params);
+
+impl Solver for Day7 {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day7::solve_part1(input, &self.0).map(Answer::from)
+    }
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day7::solve_part2(input, &self.0).map(Answer::from)
+    }
+
+    fn version(&self) -> String {
+        format!("1 {:?}", self.0)
+    }
+}
+
+struct Day8;
+
+impl Solver for Day8 {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day8::solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        day8::solve_part2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_all_days_in_order() {
+        let registry = Registry::new();
+        let days: Vec<_> = registry.iter().map(|(day, _)| day).collect();
+
+        // Days added later with new-day come after these
+        assert!(days.windows(2).all(|x| x[0] < x[1]));
+        assert!((1..=8).all(|x| days.contains(&x)));
+    }
+
+    #[test]
+    fn returns_solver_for_registered_day() {
+        let registry = Registry::new();
+
+        assert!(registry.get(1).is_some());
+        assert!(registry.get(25).is_none());
+    }
+
+    #[test]
+    fn solves_from_in_memory_input() {
+        let registry = Registry::new();
+        let solver = registry.get(6).unwrap();
+
+        assert_eq!(
+            Ok(Answer::Number(7)),
+            solver.part1(&mut "mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes())
+        );
+    }
+
+    #[test]
+    fn passes_config_to_solvers() {
+        let config = Config::parse("[day6]\npacket_marker_size = 14\n", &[]).unwrap();
+        let registry = Registry::with_config(&config);
+
+        assert_eq!(
+            Ok(Answer::Number(19)),
+            registry
+                .get(6)
+                .unwrap()
+                .part1(&mut "mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes())
+        );
+    }
+
+    #[test]
+    fn returns_variants_of_part() {
+        let registry = Registry::new();
+        let names: Vec<_> = registry.variants(3, Part::Two).map(|x| x.name).collect();
+
+        assert_eq!(vec!["bitset"], names);
+        assert_eq!(0, registry.variants(1, Part::One).count());
+        assert_eq!(
+            Ok(Answer::Number(7)),
+            registry
+                .variants(6, Part::One)
+                .next()
+                .unwrap()
+                .solve_guarded(&mut "mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes())
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use std::{collections::BTreeMap, fmt::Write, time::Duration};
+
+use crate::answers::Answers;
+use crate::bench;
+use crate::day5::{self, lib::Order};
+use crate::day8;
+use crate::input;
+use crate::runner::RunResult;
+use crate::solver::Part;
+use crate::verify::{Outcome, Summary};
+
+/// Size of a puzzle input.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct InputSize {
+    pub lines: usize,
+    pub bytes: usize,
+}
+
+impl InputSize {
+    pub fn new(input: &str) -> Self {
+        InputSize {
+            lines: input.lines().count(),
+            bytes: input.len(),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Entry {
+    pub day: u32,
+    pub part: Part,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    /// Unknown if the input couldn't be read.
+    pub size: Option<InputSize>,
+}
+
+/// Drawing of a day's input or solution, embedded in the report as preformatted text.
+#[derive(PartialEq, Debug)]
+pub struct Artifact {
+    pub day: u32,
+    pub title: String,
+    pub content: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Report {
+    pub entries: Vec<Entry>,
+    pub artifacts: Vec<Artifact>,
+}
+
+impl Report {
+    /// Verifies the results against `answers` and reads every input once more for its size and artifacts.
+    pub fn new(results: Vec<RunResult>, answers: &Answers) -> Self {
+        let mut inputs = BTreeMap::new();
+        for result in &results {
+            inputs
+                .entry(result.day)
+                .or_insert_with(|| input::read_input(&result.input).ok());
+        }
+
+        let entries = results
+            .into_iter()
+            .map(|x| Entry {
+                day: x.day,
+                part: x.part,
+                elapsed: x.elapsed,
+                size: inputs[&x.day].as_deref().map(InputSize::new),
+                outcome: Outcome::new(x.result, answers.get(x.day, x.part)),
+            })
+            .collect();
+        let artifacts = inputs
+            .iter()
+            .filter_map(|(&day, input)| input.as_deref().map(|x| artifacts(day, x)))
+            .flatten()
+            .collect();
+
+        Report { entries, artifacts }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut result = String::new();
+        writeln!(result, "# Advent of Code 2022\n").unwrap();
+        writeln!(result, "{}\n", self.summary()).unwrap();
+        writeln!(
+            result,
+            "| Day | Part | Answer | Status | Time | Lines | Bytes |"
+        )
+        .unwrap();
+        writeln!(result, "|---:|---:|---|---|---:|---:|---:|").unwrap();
+        for row in self.rows() {
+            let cells: Vec<String> = row.iter().map(|x| x.replace('|', "\\|")).collect();
+            writeln!(result, "| {} |", cells.join(" | ")).unwrap();
+        }
+
+        for artifact in &self.artifacts {
+            write!(
+                result,
+                "\n## Day {}: {}\n\n```\n{}\n```\n",
+                artifact.day, artifact.title, artifact.content
+            )
+            .unwrap();
+        }
+
+        result
+    }
+
+    /// Standalone page without external stylesheets or scripts.
+    pub fn to_html(&self) -> String {
+        let mut result = String::new();
+        result.push_str(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Advent of Code 2022</title>\n<style>\n\
+             body { font-family: sans-serif; }\n\
+             table { border-collapse: collapse; }\n\
+             th, td { border: 1px solid #ccc; padding: 2px 8px; }\n\
+             td.number { text-align: right; }\n\
+             </style>\n</head>\n<body>\n<h1>Advent of Code 2022</h1>\n",
+        );
+        writeln!(result, "<p>{}</p>", escape(&self.summary())).unwrap();
+        result.push_str(
+            "<table>\n<tr><th>Day</th><th>Part</th><th>Answer</th><th>Status</th><th>Time</th>\
+             <th>Lines</th><th>Bytes</th></tr>\n",
+        );
+        for row in self.rows() {
+            result.push_str("<tr>");
+            for (index, cell) in row.iter().enumerate() {
+                let class = match index {
+                    0 | 1 | 4 | 5 | 6 => " class=\"number\"",
+                    _ => "",
+                };
+                write!(result, "<td{}>{}</td>", class, escape(cell)).unwrap();
+            }
+            result.push_str("</tr>\n");
+        }
+        result.push_str("</table>\n");
+
+        for artifact in &self.artifacts {
+            write!(
+                result,
+                "<h2>Day {}: {}</h2>\n<pre>{}</pre>\n",
+                artifact.day,
+                escape(&artifact.title),
+                escape(&artifact.content)
+            )
+            .unwrap();
+        }
+        result.push_str("</body>\n</html>\n");
+
+        result
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} parts: {}",
+            self.entries.len(),
+            Summary::from_outcomes(self.entries.iter().map(|x| &x.outcome))
+        )
+    }
+
+    /// Cells of the table as plain text.
+    fn rows(&self) -> Vec<[String; 7]> {
+        self.entries
+            .iter()
+            .map(|x| {
+                let answer = match &x.outcome {
+                    Outcome::Pass(answer) | Outcome::Unverified(answer) => answer.to_string(),
+                    Outcome::Mismatch { expected, actual } => {
+                        format!("{} (expected {})", actual, expected)
+                    }
+                    Outcome::Fail(error) => error.to_string(),
+                };
+                let size = |f: fn(&InputSize) -> usize| {
+                    x.size
+                        .as_ref()
+                        .map_or("-".to_string(), |x| f(x).to_string())
+                };
+
+                [
+                    x.day.to_string(),
+                    x.part.to_string(),
+                    answer,
+                    x.outcome.label().to_string(),
+                    bench::format_duration(x.elapsed),
+                    size(|x| x.lines),
+                    size(|x| x.bytes),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Drawings of the days that have them, days whose input can't be solved have none.
+pub fn artifacts(day: u32, input: &str) -> Vec<Artifact> {
+    let drawings: Vec<(&str, Option<String>)> = match day {
+        5 => vec![
+            (
+                "Crate stacks before rearranging",
+                day5::draw(input.as_bytes(), None).ok(),
+            ),
+            (
+                "Crate stacks after part 1",
+                day5::draw(input.as_bytes(), Some(Order::Lifo)).ok(),
+            ),
+            (
+                "Crate stacks after part 2",
+                day5::draw(input.as_bytes(), Some(Order::Fifo)).ok(),
+            ),
+        ],
+        8 => vec![(
+            "Trees visible from outside the grid",
+            day8::draw_visibility(input.as_bytes()).ok(),
+        )],
+        _ => Vec::new(),
+    };
+
+    drawings
+        .into_iter()
+        .filter_map(|(title, content)| {
+            content.map(|content| Artifact {
+                day,
+                title: title.to_string(),
+                content,
+            })
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::solver::Answer;
+
+    fn result(day: u32, part: Part, result: Result<Answer, Error>) -> RunResult {
+        RunResult {
+            day,
+            part,
+            input: format!("examples/day{}.txt", day),
+            result,
+            elapsed: Duration::from_micros(1500),
+            explanation: Vec::new(),
+            cached: false,
+        }
+    }
+
+    fn report() -> Report {
+        let answers = Answers::parse("[day5]\npart1 = \"CMZ\"\npart2 = \"XYZ\"\n").unwrap();
+        Report::new(
+            vec![
+                result(5, Part::One, Ok(Answer::Text("CMZ".to_string()))),
+                result(5, Part::Two, Ok(Answer::Text("MCD".to_string()))),
+                result(6, Part::One, Err(Error::parse("a|b"))),
+                result(6, Part::Two, Ok(Answer::Number(19))),
+            ],
+            &answers,
+        )
+    }
+
+    #[test]
+    fn verifies_results_and_measures_inputs() {
+        let report = report();
+
+        let statuses: Vec<&str> = report.entries.iter().map(|x| x.outcome.label()).collect();
+        assert_eq!(vec!["pass", "mismatch", "fail", "unverified"], statuses);
+        assert_eq!(
+            Some(InputSize {
+                lines: 1,
+                bytes: 31
+            }),
+            report.entries[2].size
+        );
+    }
+
+    #[test]
+    fn embeds_artifacts() {
+        let report = report();
+        let titles: Vec<(u32, &str)> = report
+            .artifacts
+            .iter()
+            .map(|x| (x.day, x.title.as_str()))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (5, "Crate stacks before rearranging"),
+                (5, "Crate stacks after part 1"),
+                (5, "Crate stacks after part 2"),
+            ],
+            titles
+        );
+        assert_eq!(
+            "#####\n###.#\n##.##\n#.#.#\n#####",
+            artifacts(8, "30373\n25512\n65332\n33549\n35390\n")[0].content
+        );
+        assert!(artifacts(5, "").is_empty());
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = report().to_markdown();
+
+        assert!(markdown.contains("4 parts: 1 passed, 1 mismatched, 1 failed, 1 unverified"));
+        assert!(markdown.contains("| 5 | 1 | CMZ | pass | 1500.0µs | 9 | 125 |\n"));
+        assert!(markdown.contains("| 5 | 2 | MCD (expected XYZ) | mismatch |"));
+        assert!(markdown.contains("| 6 | 1 | a\\|b | fail |"));
+        assert!(markdown.contains(
+            "## Day 5: Crate stacks after part 2\n\n```\n        [D]\n        [N]\n        [Z]\n\
+             [M] [C] [P]\n 1   2   3\n```\n"
+        ));
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = report().to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td class=\"number\">5</td><td class=\"number\">1</td><td>CMZ</td>"));
+        assert!(html.contains("<pre>    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3</pre>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+}
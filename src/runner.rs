@@ -0,0 +1,399 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use tracing::{debug, debug_span, error, info, info_span};
+
+use crate::cache::{self, Cache};
+use crate::error::Error;
+use crate::input;
+use crate::pool;
+use crate::solver::{self, Answer, Part, Solver};
+use crate::timing;
+use crate::trace;
+
+#[derive(PartialEq, Debug)]
+pub struct RunResult {
+    pub day: u32,
+    pub part: Part,
+    pub input: String,
+    pub result: Result<Answer, Error>,
+    pub elapsed: Duration,
+    /// Steps the solver narrated on the way to its answer, empty unless explaining.
+    pub explanation: Vec<String>,
+    /// Whether the answer was taken from the cache instead of solving, its elapsed time is zero then.
+    pub cached: bool,
+}
+
+/// Runs the given parts of a day. The input is read once and shared by all parts, if it can't be read every part
+/// fails with the same error. With `explain` the steps traced by the solver are collected as well.
+///
+/// With a `cache`, parts it has an answer for are not solved again unless explaining, and new answers are added to it.
+pub fn run_day(
+    day: u32,
+    solver: &dyn Solver,
+    path: &str,
+    parts: &[Part],
+    explain: bool,
+    cache: Option<&Cache>,
+) -> Vec<RunResult> {
+    let _span = info_span!("day", day).entered();
+    let input = debug_span!("read", path).in_scope(|| {
+        let result = input::read_input(path);
+        if let Ok(input) = &result {
+            debug!(
+                lines = input.lines().count(),
+                bytes = input.len(),
+                "input read"
+            );
+        }
+        result
+    });
+
+    parts
+        .iter()
+        .map(|&part| {
+            let _span = info_span!("part", part = part.number()).entered();
+            let key = match (&input, cache) {
+                (Ok(input), Some(_)) => Some(cache::Key::new(day, part, input, &solver.version())),
+                _ => None,
+            };
+            let cached = match (&key, cache) {
+                (Some(key), Some(cache)) if !explain => cache.get(key),
+                _ => None,
+            };
+            if let Some(answer) = cached {
+                debug!(%answer, "answer taken from cache");
+                return RunResult {
+                    day,
+                    part,
+                    input: path.to_string(),
+                    result: Ok(answer),
+                    elapsed: Duration::ZERO,
+                    explanation: Vec::new(),
+                    cached: true,
+                };
+            }
+
+            let (result, elapsed, explanation) = match &input {
+                Ok(input) => {
+                    let solve = || solver::solve_guarded(solver, part, &mut input.as_bytes());
+                    let ((result, explanation), timings) = timing::measure(|| {
+                        if explain {
+                            trace::record(solve)
+                        } else {
+                            (solve(), Vec::new())
+                        }
+                    });
+                    (result, timings.total(), explanation)
+                }
+                Err(error) => (Err(error.clone()), Duration::ZERO, Vec::new()),
+            };
+            match &result {
+                Ok(answer) => info!(%answer, ?elapsed, "solved"),
+                Err(error) => error!(%error, "failed"),
+            }
+            if let (Some(key), Some(cache), Ok(answer)) = (key, cache, &result) {
+                cache.insert(key, answer.clone());
+            }
+
+            RunResult {
+                day,
+                part,
+                input: path.to_string(),
+                result,
+                elapsed,
+                explanation,
+                cached: false,
+            }
+        })
+        .collect()
+}
+
+pub struct Job<'a> {
+    pub day: u32,
+    pub solver: &'a dyn Solver,
+    pub path: String,
+}
+
+/// Runs the given parts of every job on up to `workers` threads. Results are in the order of `jobs`, regardless of
+/// which day finishes first.
+pub fn run_days(
+    jobs: &[Job],
+    parts: &[Part],
+    workers: usize,
+    explain: bool,
+    cache: Option<&Cache>,
+) -> Vec<RunResult> {
+    pool::map(jobs, workers, |x| {
+        run_day(x.day, x.solver, &x.path, parts, explain, cache)
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    version: u32,
+    results: Vec<ReportEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct ReportEntry<'a> {
+    day: u32,
+    part: u8,
+    input: &'a str,
+    answer: Option<&'a Answer>,
+    #[serde(rename = "type")]
+    kind: Option<&'static str>,
+    elapsed_ns: u64,
+    cached: bool,
+    error: Option<String>,
+    explanation: &'a [String],
+}
+
+/// Version of the JSON schema, bumped whenever a field is renamed, removed or changes its meaning.
+pub const JSON_VERSION: u32 = 1;
+
+/// Serializes results to pretty printed JSON. Every entry has all the fields, `answer` and `type` are null for
+/// failed parts, `error` is null for solved ones and `explanation` is empty unless explaining. `cached` tells
+/// whether the answer was taken from the cache.
+pub fn to_json(results: &[RunResult]) -> String {
+    let report = Report {
+        version: JSON_VERSION,
+        results: results
+            .iter()
+            .map(|x| ReportEntry {
+                day: x.day,
+                part: x.part.number(),
+                input: &x.input,
+                answer: x.result.as_ref().ok(),
+                kind: x.result.as_ref().ok().map(Answer::kind),
+                elapsed_ns: x.elapsed.as_nanos() as u64,
+                cached: x.cached,
+                error: x.result.as_ref().err().map(|x| x.to_string()),
+                explanation: &x.explanation,
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&report).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{self, Registry};
+
+    #[test]
+    fn runs_selected_parts() {
+        let registry = Registry::new();
+        let results = run_day(
+            6,
+            registry.get(6).unwrap(),
+            "examples/day6.txt",
+            &[Part::Two],
+            false,
+            None,
+        );
+
+        assert_eq!(1, results.len());
+        assert_eq!(6, results[0].day);
+        assert_eq!(Part::Two, results[0].part);
+        assert_eq!(Ok(Answer::Number(19)), results[0].result);
+    }
+
+    #[test]
+    fn collects_explanation_only_if_asked() {
+        let registry = Registry::new();
+        let solver = registry.get(6).unwrap();
+
+        let explained = run_day(6, solver, "examples/day6.txt", &[Part::One], true, None);
+        assert_eq!(
+            vec!["marker 'jpqm' ends after 7 characters"],
+            explained[0].explanation
+        );
+
+        let plain = run_day(6, solver, "examples/day6.txt", &[Part::One], false, None);
+        assert!(plain[0].explanation.is_empty());
+    }
+
+    #[test]
+    fn fails_every_part_if_input_is_missing() {
+        let registry = Registry::new();
+        let results = run_day(
+            1,
+            registry.get(1).unwrap(),
+            "missing.txt",
+            &Part::ALL,
+            false,
+            None,
+        );
+
+        assert_eq!(2, results.len());
+        for result in results {
+            assert!(matches!(result.result, Err(Error::Io(_))));
+            assert_eq!(Duration::ZERO, result.elapsed);
+        }
+    }
+
+    #[test]
+    fn reuses_cached_answers() {
+        let registry = Registry::new();
+        let solver = registry.get(6).unwrap();
+        let cache = Cache::new("unused.json");
+        let input = input::read_input("examples/day6.txt").unwrap();
+        cache.insert(
+            cache::Key::new(6, Part::One, &input, &solver.version()),
+            Answer::Number(42),
+        );
+
+        let results = run_day(
+            6,
+            solver,
+            "examples/day6.txt",
+            &Part::ALL,
+            false,
+            Some(&cache),
+        );
+        assert_eq!(Ok(Answer::Number(42)), results[0].result);
+        assert!(results[0].cached);
+        assert_eq!(Ok(Answer::Number(19)), results[1].result);
+        assert!(!results[1].cached);
+
+        let key = cache::Key::new(6, Part::Two, &input, &solver.version());
+        assert_eq!(Some(Answer::Number(19)), cache.get(&key));
+
+        let explained = run_day(
+            6,
+            solver,
+            "examples/day6.txt",
+            &[Part::One],
+            true,
+            Some(&cache),
+        );
+        assert_eq!(Ok(Answer::Number(7)), explained[0].result);
+    }
+
+    #[test]
+    fn ignores_answers_of_other_solver_versions() {
+        let cache = Cache::new("unused.json");
+        let input = input::read_input("examples/day6.txt").unwrap();
+        cache.insert(
+            cache::Key::new(6, Part::One, &input, "0"),
+            Answer::Number(42),
+        );
+
+        let registry = Registry::new();
+        let results = run_day(
+            6,
+            registry.get(6).unwrap(),
+            "examples/day6.txt",
+            &[Part::One],
+            false,
+            Some(&cache),
+        );
+
+        assert_eq!(Ok(Answer::Number(7)), results[0].result);
+        assert!(!results[0].cached);
+    }
+
+    #[test]
+    fn runs_days_in_parallel_in_order() {
+        let registry = Registry::new();
+        let jobs: Vec<Job> = registry
+            .iter()
+            .map(|(day, solver)| Job {
+                day,
+                solver,
+                path: registry::example_input_path(day),
+            })
+            .collect();
+
+        let results = run_days(&jobs, &Part::ALL, 3, false, None);
+
+        let order: Vec<(u32, Part)> = results.iter().map(|x| (x.day, x.part)).collect();
+        let expected: Vec<(u32, Part)> = registry
+            .iter()
+            .flat_map(|(day, _)| Part::ALL.map(|part| (day, part)))
+            .collect();
+        assert_eq!(expected, order);
+    }
+
+    #[test]
+    fn isolates_errors_per_day() {
+        let registry = Registry::new();
+        let jobs = [
+            Job {
+                day: 1,
+                solver: registry.get(1).unwrap(),
+                path: "missing.txt".to_string(),
+            },
+            Job {
+                day: 6,
+                solver: registry.get(6).unwrap(),
+                path: registry::example_input_path(6),
+            },
+        ];
+
+        let results = run_days(&jobs, &[Part::One], 2, false, None);
+
+        assert!(matches!(results[0].result, Err(Error::Io(_))));
+        assert_eq!(Ok(Answer::Number(7)), results[1].result);
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let results = [
+            RunResult {
+                day: 5,
+                part: Part::One,
+                input: "inputs/day5.txt".to_string(),
+                result: Ok(Answer::Text("CMZ".to_string())),
+                elapsed: Duration::from_nanos(10),
+                explanation: vec!["marker found".to_string()],
+                cached: true,
+            },
+            RunResult {
+                day: 7,
+                part: Part::Two,
+                input: "inputs/day7.txt".to_string(),
+                result: Err(Error::NoSolution(
+                    "no dir frees up enough space".to_string(),
+                )),
+                elapsed: Duration::from_nanos(20),
+                explanation: Vec::new(),
+                cached: false,
+            },
+        ];
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&results)).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "version": 1,
+                "results": [{
+                    "day": 5,
+                    "part": 1,
+                    "input": "inputs/day5.txt",
+                    "answer": "CMZ",
+                    "type": "text",
+                    "elapsed_ns": 10,
+                    "cached": true,
+                    "error": null,
+                    "explanation": ["marker found"]
+                }, {
+                    "day": 7,
+                    "part": 2,
+                    "input": "inputs/day7.txt",
+                    "answer": null,
+                    "type": null,
+                    "elapsed_ns": 20,
+                    "cached": false,
+                    "error": "no solution: no dir frees up enough space",
+                    "explanation": []
+                }]
+            }),
+            json
+        );
+    }
+}
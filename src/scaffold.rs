@@ -0,0 +1,391 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::error::Error;
+use crate::import;
+use crate::registry;
+
+const MAX_WIDTH: usize = 100;
+
+/// Generates the module of a new day, registers it with the registry, creates empty input and example files and
+/// adds a section for the example answers to the manifest. Returns the paths of the created and modified files,
+/// relative to `root`.
+pub fn create(root: &Path, day: u32) -> Result<Vec<PathBuf>, Error> {
+    let module_path = PathBuf::from(format!("src/day{}.rs", day));
+    let lib_module_path = PathBuf::from(format!("src/day{}/lib.rs", day));
+    let crate_path = PathBuf::from("src/lib.rs");
+    let registry_path = PathBuf::from("src/registry.rs");
+
+    if root.join(&module_path).exists() {
+        return Err(already_exists(module_path.display()));
+    }
+
+    let crate_source = register_module(&fs::read_to_string(root.join(&crate_path))?, day)?;
+    let registry_source = register_solver(&fs::read_to_string(root.join(&registry_path))?, day)?;
+
+    fs::create_dir_all(root.join(format!("src/day{}", day)))?;
+    fs::write(root.join(&module_path), module_source(day))?;
+    fs::write(root.join(&lib_module_path), lib_module_source(day))?;
+    fs::write(root.join(&crate_path), crate_source)?;
+    fs::write(root.join(&registry_path), registry_source)?;
+    let mut paths = vec![module_path, lib_module_path, crate_path, registry_path];
+
+    for fixture in [
+        registry::default_input_path(day),
+        registry::example_input_path(day),
+    ] {
+        let path = root.join(&fixture);
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, "")?;
+            paths.push(PathBuf::from(fixture));
+        }
+    }
+
+    let answers_path = PathBuf::from(registry::EXAMPLE_ANSWERS_PATH);
+    let manifest = fs::read_to_string(root.join(&answers_path)).unwrap_or_default();
+    let updated = import::set_answers(&manifest, day, &[])?;
+    if updated != manifest {
+        fs::write(root.join(&answers_path), updated)?;
+        paths.push(answers_path);
+    }
+
+    Ok(paths)
+}
+
+fn already_exists(what: impl fmt::Display) -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("{} already exists", what),
+    ))
+}
+
+fn malformed(path: &str, message: &str) -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path, message),
+    ))
+}
+
+pub fn module_source(day: u32) -> String {
+    format!(
+        r#"pub mod lib;
+
+use std::io::BufRead;
+
+use crate::error::Error;
+use crate::input;
+use crate::timing::{{self, Phase}};
+
+pub fn run_part1(path: &str) -> Result<usize, Error> {{
+    solve_part1(input::open(path)?)
+}}
+
+pub fn run_part2(path: &str) -> Result<usize, Error> {{
+    solve_part2(input::open(path)?)
+}}
+
+pub fn solve_part1<R: BufRead>(reader: R) -> Result<usize, Error> {{
+    let _lines = parse_input(reader)?;
+    Err(Error::NoSolution("not implemented yet".to_string()))
+}}
+
+pub fn solve_part2<R: BufRead>(reader: R) -> Result<usize, Error> {{
+    let _lines = parse_input(reader)?;
+    Err(Error::NoSolution("not implemented yet".to_string()))
+}}
+
+fn parse_input<R: BufRead>(reader: R) -> Result<Vec<String>, Error> {{
+    let lines = input::read_lines(reader)?;
+    timing::enter(Phase::Solve);
+
+    Ok(lines)
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    #[ignore = "not implemented yet"]
+    fn returns_answer_for_part1() {{
+        assert_eq!(Ok(0), run_part1("examples/day{day}.txt"));
+    }}
+
+    #[test]
+    #[ignore = "not implemented yet"]
+    fn returns_answer_for_part2() {{
+        assert_eq!(Ok(0), run_part2("examples/day{day}.txt"));
+    }}
+}}
+"#
+    )
+}
+
+pub fn lib_module_source(day: u32) -> String {
+    format!("//! Types shared by both parts of day {}.\n", day)
+}
+
+/// Adds `pub mod dayN;` to the crate root, keeping the module declarations sorted.
+pub fn register_module(source: &str, day: u32) -> Result<String, Error> {
+    let name = format!("day{}", day);
+    let declaration = format!("pub mod {};", name);
+    let mut lines: Vec<&str> = source.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return Err(already_exists(format!("module {}", name)));
+    }
+
+    let position = lines
+        .iter()
+        .position(|x| declared_module(x).is_some_and(|x| x > name.as_str()))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|x| declared_module(x).is_some())
+                .map(|x| x + 1)
+        })
+        .ok_or_else(|| malformed("src/lib.rs", "no module declarations found"))?;
+    lines.insert(position, &declaration);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn declared_module(line: &str) -> Option<&str> {
+    line.strip_prefix("pub mod ")?.strip_suffix(';')
+}
+
+/// Imports the module of a new day into the registry, registers its solver and appends the solver itself.
+pub fn register_solver(source: &str, day: u32) -> Result<String, Error> {
+    let solver = format!("Day{}", day);
+    if source.contains(&format!("Box::new({})", solver)) {
+        return Err(already_exists(format!("solver for day {}", day)));
+    }
+
+    // Imports of day modules
+    let import_start = source
+        .find("use crate::{")
+        .ok_or_else(|| malformed("src/registry.rs", "day module imports not found"))?;
+    let import_end = import_start
+        + source[import_start..]
+            .find("};")
+            .ok_or_else(|| malformed("src/registry.rs", "day module imports not found"))?
+        + 2;
+    let mut modules: Vec<String> = source[import_start + "use crate::{".len()..import_end - 2]
+        .split(',')
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+        .collect();
+    modules.push(format!("day{}", day));
+    modules.sort();
+
+    // Registration in the constructor
+    let insert_end = source
+        .rfind("Box::new(Day")
+        .and_then(|x| source[x..].find('\n').map(|y| x + y + 1))
+        .ok_or_else(|| malformed("src/registry.rs", "solver registrations not found"))?;
+
+    // Solver implementation, placed before the tests
+    let tests_start = source.find("#[cfg(test)]").unwrap_or(source.len());
+
+    let mut result = String::new();
+    result.push_str(&source[..import_start]);
+    result.push_str(&format_imports(&modules));
+    result.push_str(&source[import_end..insert_end]);
+    result.push_str(&format!(
+        "        solvers.insert({}, Box::new({}));\n",
+        day, solver
+    ));
+    result.push_str(&source[insert_end..tests_start]);
+    result.push_str(&format!(
+        r#"struct {solver};
+
+impl Solver for {solver} {{
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {{
+        day{day}::solve_part1(input).map(Answer::from)
+    }}
+
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {{
+        day{day}::solve_part2(input).map(Answer::from)
+    }}
+}}
+
+"#
+    ));
+    result.push_str(&source[tests_start..]);
+
+    Ok(result)
+}
+
+/// Formats a `use crate::{..}` list the way rustfmt does: on one line if it fits, otherwise wrapped.
+fn format_imports(modules: &[String]) -> String {
+    let single_line = format!("use crate::{{{}}};", modules.join(", "));
+    if single_line.len() <= MAX_WIDTH {
+        return single_line;
+    }
+
+    let mut lines = Vec::new();
+    let mut line = String::from("   ");
+    for module in modules {
+        if line.len() + module.len() + 2 > MAX_WIDTH {
+            lines.push(line);
+            line = String::from("   ");
+        }
+        line.push(' ');
+        line.push_str(module);
+        line.push(',');
+    }
+    lines.push(line);
+
+    format!("use crate::{{\n{}\n}};", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::registry::Registry;
+
+    const CRATE: &str = "pub mod answers;\npub mod day1_part1;\npub mod day8;\npub mod error;\n";
+
+    const REGISTRY: &str = r#"use crate::{
+    day1_part1, day1_part2, day2_part1, day2_part2, day3_part1, day3_part2, day4_part1, day4_part2,
+    day5, day6, day7, day8,
+};
+
+impl Registry {
+    pub fn new() -> Self {
+        let mut solvers: BTreeMap<u32, Box<dyn Solver>> = BTreeMap::new();
+        solvers.insert(1, Box::new(Day1));
+        solvers.insert(8, Box::new(Day8));
+
+        Registry { solvers }
+    }
+}
+
+struct Day8;
+
+#[cfg(test)]
+mod tests {}
+"#;
+
+    #[test]
+    fn registers_module_in_order() {
+        let source = "pub mod day1;\npub mod day8;\npub mod error;\n";
+
+        assert_eq!(
+            Ok("pub mod day1;\npub mod day8;\npub mod day9;\npub mod error;\n".to_string()),
+            register_module(source, 9)
+        );
+        assert_eq!(
+            Ok("pub mod day1;\npub mod day10;\npub mod day8;\npub mod error;\n".to_string()),
+            register_module(source, 10)
+        );
+        assert!(register_module(source, 8).is_err());
+    }
+
+    #[test]
+    fn registers_solver() {
+        let result = register_solver(REGISTRY, 9).unwrap();
+
+        assert!(result.contains("    day5, day6, day7, day8, day9,\n};"));
+        assert!(result.contains(
+            "        solvers.insert(8, Box::new(Day8));\n        solvers.insert(9, Box::new(Day9));\n"
+        ));
+        assert!(result.contains("struct Day8;\n\nstruct Day9;\n\nimpl Solver for Day9 {"));
+        assert!(result.contains("day9::solve_part2(input).map(Answer::from)"));
+        assert!(result.ends_with("}\n\n#[cfg(test)]\nmod tests {}\n"));
+
+        assert!(register_solver(REGISTRY, 8).is_err());
+    }
+
+    #[test]
+    fn formats_imports_like_rustfmt() {
+        assert_eq!(
+            "use crate::{day1, day2};",
+            format_imports(&["day1".to_string(), "day2".to_string()])
+        );
+
+        let modules: Vec<String> = (1..=4)
+            .flat_map(|x| [format!("day{}_part1", x), format!("day{}_part2", x)])
+            .chain((5..=8).map(|x| format!("day{}", x)))
+            .collect();
+        assert!(REGISTRY.starts_with(&format_imports(&modules)));
+    }
+
+    #[test]
+    fn creates_day() {
+        let root = env::temp_dir().join(format!("aoc2022-scaffold-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), CRATE).unwrap();
+        fs::write(root.join("src/registry.rs"), REGISTRY).unwrap();
+
+        let paths = create(&root, 9).unwrap();
+
+        assert_eq!(
+            vec![
+                PathBuf::from("src/day9.rs"),
+                PathBuf::from("src/day9/lib.rs"),
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("src/registry.rs"),
+                PathBuf::from("inputs/day9.txt"),
+                PathBuf::from("examples/day9.txt"),
+                PathBuf::from("examples/answers.toml"),
+            ],
+            paths
+        );
+        assert_eq!(
+            module_source(9),
+            fs::read_to_string(root.join("src/day9.rs")).unwrap()
+        );
+        assert_eq!(
+            "pub mod answers;\npub mod day1_part1;\npub mod day8;\npub mod day9;\npub mod error;\n",
+            fs::read_to_string(root.join("src/lib.rs")).unwrap()
+        );
+        assert_eq!(
+            "[day9]\n",
+            fs::read_to_string(root.join("examples/answers.toml")).unwrap()
+        );
+        assert!(matches!(create(&root, 9), Err(Error::Io(_))));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn creates_day_in_copy_of_crate() {
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let root = env::temp_dir().join(format!("aoc2022-scaffold-crate-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("examples")).unwrap();
+        for path in ["src/lib.rs", "src/registry.rs", "examples/answers.toml"] {
+            fs::copy(source.join(path), root.join(path)).unwrap();
+        }
+        let registry = Registry::new();
+        let day = (1..=25).find(|&x| registry.get(x).is_none()).unwrap();
+
+        create(&root, day).unwrap();
+
+        let crate_source = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        let modules: Vec<&str> = crate_source.lines().filter_map(declared_module).collect();
+        assert!(modules.contains(&format!("day{}", day).as_str()));
+        assert!(modules.windows(2).all(|x| x[0] < x[1]));
+        let registry_source = fs::read_to_string(root.join("src/registry.rs")).unwrap();
+        let imports = registry_source.split("use crate::{").nth(1).unwrap();
+        let imports = &imports[..imports.find("};").unwrap()];
+        assert!(imports.contains(&format!(" day{},", day)));
+        assert!(registry_source.contains(&format!(
+            "        solvers.insert({day}, Box::new(Day{day}));\n\n        let variants"
+        )));
+        assert!(registry_source.contains(&format!("impl Solver for Day{} {{", day)));
+        let manifest = fs::read_to_string(root.join("examples/answers.toml")).unwrap();
+        assert!(manifest.contains(&format!("\n[day{}]\n", day)));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
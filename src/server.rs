@@ -0,0 +1,406 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+
+use crate::error::Error;
+use crate::registry::Registry;
+use crate::solver::{self, Part};
+use crate::timing;
+
+/// Longest request line or header line accepted.
+const MAX_LINE_LENGTH: u64 = 8 * 1024;
+/// Most headers accepted in a request.
+const MAX_HEADERS: usize = 100;
+/// Largest puzzle input accepted, real ones are a few dozen kilobytes.
+const MAX_BODY_LENGTH: usize = 16 * 1024 * 1024;
+/// Clients that stall for longer are disconnected, so they can't keep a worker busy forever.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(PartialEq, Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Headers in the order received, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Response {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            431 => "Request Header Fields Too Large",
+            422 => "Unprocessable Entity",
+            501 => "Not Implemented",
+            _ => "Internal Server Error",
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let body = self.body.to_string();
+        write!(writer, "HTTP/1.1 {} {}\r\n", self.status, self.reason())?;
+        write!(writer, "Content-Type: application/json\r\n")?;
+        write!(writer, "Content-Length: {}\r\n", body.len())?;
+        if self.status == 405 {
+            write!(writer, "Allow: POST\r\n")?;
+        }
+        write!(writer, "Connection: close\r\n\r\n{}", body)?;
+        writer.flush()
+    }
+}
+
+/// Answers requests on `listener` with `workers` threads, each handling one connection at a time. Further
+/// connections wait until a worker is free. Connections are closed after one request.
+pub fn serve(listener: TcpListener, registry: &Registry, workers: usize) {
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            scope.spawn(|| {
+                // Failing to accept a connection only affects that client
+                for stream in listener.incoming().flatten() {
+                    let _ = handle(stream, registry);
+                }
+            });
+        }
+    });
+}
+
+fn handle(stream: TcpStream, registry: &Registry) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let response = match read_request(BufReader::new(&stream)) {
+        Ok(request) => respond(registry, &request),
+        Err(response) => response,
+    };
+    response.write_to(&stream)
+}
+
+/// Reads a request with a body of known length, chunked bodies aren't supported.
+pub fn read_request<R: BufRead>(mut reader: R) -> Result<Request, Response> {
+    let request_line = read_line(&mut reader)?;
+    let (method, path) = match request_line.split(' ').collect::<Vec<_>>().as_slice() {
+        [method, target, version] if version.starts_with("HTTP/1.") => {
+            // Query strings are ignored
+            let path = target.split('?').next().unwrap_or_default();
+            (method.to_string(), path.to_string())
+        }
+        _ => return Err(Response::error(400, "malformed request line")),
+    };
+
+    let mut headers = Vec::new();
+    let mut content_length = None;
+    loop {
+        let line = read_line(&mut reader)?;
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(Response::error(431, "too many headers"));
+        }
+
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| Response::error(400, format!("malformed header '{}'", line)))?;
+        let name = name.trim().to_ascii_lowercase();
+        match name.as_str() {
+            "content-length" => {
+                let length: usize = value.trim().parse().map_err(|_| {
+                    Response::error(400, format!("invalid content length '{}'", value.trim()))
+                })?;
+                content_length = Some(length);
+            }
+            "transfer-encoding" => {
+                return Err(Response::error(501, "transfer encodings are not supported"))
+            }
+            _ => {}
+        }
+        headers.push((name, value.trim().to_string()));
+    }
+
+    let body = match content_length {
+        Some(length) if length > MAX_BODY_LENGTH => {
+            return Err(Response::error(413, "input is too large"))
+        }
+        Some(length) => {
+            let mut body = vec![0; length];
+            reader
+                .read_exact(&mut body)
+                .map_err(|_| Response::error(400, "incomplete body"))?;
+            body
+        }
+        None if method == "POST" => return Err(Response::error(411, "content length required")),
+        None => Vec::new(),
+    };
+
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String, Response> {
+    let mut line = String::new();
+    reader
+        .by_ref()
+        .take(MAX_LINE_LENGTH)
+        .read_line(&mut line)
+        .map_err(|_| Response::error(400, "malformed request"))?;
+    if !line.ends_with('\n') {
+        return Err(Response::error(400, "incomplete request"));
+    }
+
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Solves `POST /day/{n}/part/{p}` with the body as the puzzle input.
+pub fn respond(registry: &Registry, request: &Request) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let ["day", day, "part", part] = segments.as_slice() else {
+        return Response::error(404, format!("no route for '{}'", request.path));
+    };
+    if request.method != "POST" {
+        return Response::error(405, format!("method {} not allowed", request.method));
+    }
+
+    let Some((day, solver)) = day
+        .parse()
+        .ok()
+        .and_then(|x| registry.get(x).map(|solver| (x, solver)))
+    else {
+        return Response::error(404, format!("day '{}' is not implemented", day));
+    };
+    let Some(part) = part.parse::<u8>().ok().and_then(|x| Part::try_from(x).ok()) else {
+        return Response::error(404, format!("invalid part '{}'", part));
+    };
+
+    let (result, timings) =
+        timing::measure(|| solver::solve_guarded(solver, part, &mut request.body.as_slice()));
+    match result {
+        Ok(answer) => Response {
+            status: 200,
+            body: json!({
+                "day": day,
+                "part": part.number(),
+                "answer": answer,
+                "type": answer.kind(),
+                "elapsed_ns": timings.total().as_nanos() as u64,
+            }),
+        },
+        Err(error) => Response {
+            status: status_of(&error),
+            body: json!({
+                "day": day,
+                "part": part.number(),
+                "error": error.to_string(),
+            }),
+        },
+    }
+}
+
+/// Malformed inputs are the client's fault, so are well-formed ones the puzzle rules don't allow.
+fn status_of(error: &Error) -> u16 {
+    match error {
+        Error::Io(_) | Error::Parse { .. } => 400,
+        Error::DuplicateStack(_)
+        | Error::UnknownStack { .. }
+        | Error::NotEnoughCrates { .. }
+        | Error::DuplicateDir(_)
+        | Error::DuplicateFile(_)
+        | Error::MissingDir(_)
+        | Error::NoSolution(_) => 422,
+        Error::Panic(_) | Error::Http(_) => 500,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, net::SocketAddr};
+
+    use super::*;
+
+    fn start_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, &Registry::new(), 2));
+
+        address
+    }
+
+    fn send(address: SocketAddr, request: &str) -> (String, Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status_line = head.lines().next().unwrap().to_string();
+        (status_line, serde_json::from_str(body).unwrap())
+    }
+
+    fn post(address: SocketAddr, path: &str, body: &str) -> (String, Value) {
+        send(
+            address,
+            &format!(
+                "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+                path,
+                body.len(),
+                body
+            ),
+        )
+    }
+
+    #[test]
+    fn answers_over_http() {
+        let address = start_server();
+        let input = fs::read_to_string("examples/day5.txt").unwrap();
+
+        let (status, body) = post(address, "/day/5/part/2", &input);
+
+        assert_eq!("HTTP/1.1 200 OK", status);
+        assert_eq!("MCD", body["answer"]);
+        assert_eq!("text", body["type"]);
+        assert_eq!(5, body["day"]);
+        assert_eq!(2, body["part"]);
+    }
+
+    #[test]
+    fn returns_client_errors() {
+        let address = start_server();
+
+        let (status, body) = post(address, "/day/1/part/1", "1000\nabc\n");
+        assert_eq!("HTTP/1.1 400 Bad Request", status);
+        assert_eq!("line 2: invalid calories 'abc'", body["error"]);
+
+        let (status, _) = post(address, "/day/6/part/1", "aaaa");
+        assert_eq!("HTTP/1.1 422 Unprocessable Entity", status);
+
+        let (status, _) = post(address, "/day/25/part/1", "");
+        assert_eq!("HTTP/1.1 404 Not Found", status);
+
+        let (status, _) = send(address, "GET /day/1/part/1 HTTP/1.1\r\n\r\n");
+        assert_eq!("HTTP/1.1 405 Method Not Allowed", status);
+
+        let (status, _) = send(address, "POST /day/1/part/1 HTTP/1.1\r\n\r\n");
+        assert_eq!("HTTP/1.1 411 Length Required", status);
+    }
+
+    #[test]
+    fn answers_while_a_client_stalls() {
+        let address = start_server();
+        let _stalled = TcpStream::connect(address).unwrap();
+
+        let (status, _) = post(address, "/day/6/part/1", "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+
+        assert_eq!("HTTP/1.1 200 OK", status);
+    }
+
+    #[test]
+    fn reads_request() {
+        let request = "POST /day/1/part/2?x=1 HTTP/1.1\r\ncontent-length: 3\r\n\r\n1\n2";
+
+        assert_eq!(
+            Ok(Request {
+                method: "POST".to_string(),
+                path: "/day/1/part/2".to_string(),
+                headers: vec![("content-length".to_string(), "3".to_string())],
+                body: b"1\n2".to_vec(),
+            }),
+            read_request(request.as_bytes())
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        for (status, request) in [
+            (400, "POST /day/1/part/1\r\n\r\n"),
+            (
+                400,
+                "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: x\r\n\r\n",
+            ),
+            (
+                400,
+                "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 5\r\n\r\n1",
+            ),
+            (400, "POST /day/1/part/1 HTTP/1.1\r\nno header\r\n\r\n"),
+            (
+                413,
+                "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n",
+            ),
+            (
+                501,
+                "POST /day/1/part/1 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n",
+            ),
+            (
+                431,
+                &format!(
+                    "POST /day/1/part/1 HTTP/1.1\r\n{}\r\n",
+                    "X: 1\r\n".repeat(MAX_HEADERS + 1)
+                ),
+            ),
+        ] {
+            assert_eq!(
+                Some(status),
+                read_request(request.as_bytes()).err().map(|x| x.status),
+                "{:?}",
+                request
+            );
+        }
+    }
+
+    #[test]
+    fn routes_requests() {
+        let registry = Registry::new();
+        let request = |method: &str, path: &str| Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: Vec::new(),
+            body: b"mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_vec(),
+        };
+
+        assert_eq!(
+            200,
+            respond(&registry, &request("POST", "/day/6/part/1")).status
+        );
+        assert_eq!(
+            200,
+            respond(&registry, &request("POST", "day/6/part/2/")).status
+        );
+        assert_eq!(
+            404,
+            respond(&registry, &request("POST", "/day/6/part/3")).status
+        );
+        assert_eq!(
+            404,
+            respond(&registry, &request("POST", "/day/x/part/1")).status
+        );
+        assert_eq!(404, respond(&registry, &request("POST", "/days/6")).status);
+        assert_eq!(
+            405,
+            respond(&registry, &request("PUT", "/day/6/part/1")).status
+        );
+    }
+}
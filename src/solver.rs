@@ -0,0 +1,192 @@
+use std::{
+    fmt,
+    io::BufRead,
+    panic::{self, AssertUnwindSafe},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Answer {
+    /// Name of the answer type, as used in machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Number(_) => "number",
+            Answer::Text(_) => "text",
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("invalid part '{}'", value)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Solvers are shared between the worker threads when days run in parallel.
+pub trait Solver: Sync {
+    fn part1(&self, input: &mut dyn BufRead) -> Result<Answer, Error>;
+    fn part2(&self, input: &mut dyn BufRead) -> Result<Answer, Error>;
+
+    fn solve(&self, part: Part, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+
+    /// Identifies what the solver computes, cached answers are only reused by the same version. Bump it whenever a
+    /// change can give different answers, and include any parameters the answers depend on.
+    fn version(&self) -> String {
+        "1".to_string()
+    }
+}
+
+/// Alternative implementation of one part of a day, for example an optimized one. The day's registered solver
+/// serves as its reference.
+pub struct Variant {
+    pub day: u32,
+    pub part: Part,
+    pub name: &'static str,
+    pub solve: fn(&mut dyn BufRead) -> Result<Answer, Error>,
+}
+
+impl Variant {
+    /// Same as `solve_guarded`, for the variant.
+    pub fn solve_guarded(&self, input: &mut dyn BufRead) -> Result<Answer, Error> {
+        guarded(|| (self.solve)(input))
+    }
+}
+
+/// Same as `Solver::solve`, but a panic is turned into an error so that a broken solver only fails its own part.
+pub fn solve_guarded(
+    solver: &dyn Solver,
+    part: Part,
+    input: &mut dyn BufRead,
+) -> Result<Answer, Error> {
+    guarded(|| solver.solve(part, input))
+}
+
+fn guarded<F: FnOnce() -> Result<Answer, Error>>(f: F) -> Result<Answer, Error> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|x| x.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown cause".to_string());
+        Err(Error::Panic(message))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Panicking;
+
+    impl Solver for Panicking {
+        fn part1(&self, _: &mut dyn BufRead) -> Result<Answer, Error> {
+            panic!("part {} is broken", 1)
+        }
+
+        fn part2(&self, _: &mut dyn BufRead) -> Result<Answer, Error> {
+            Ok(Answer::Number(2))
+        }
+    }
+
+    #[test]
+    fn converts_to_answer() {
+        assert_eq!(Answer::Number(-1), (-1i32).into());
+        assert_eq!(Answer::Number(2), 2usize.into());
+        assert_eq!(Answer::Text("CMZ".to_string()), "CMZ".to_string().into());
+    }
+
+    #[test]
+    fn displays_answer() {
+        assert_eq!("123", Answer::Number(123).to_string());
+        assert_eq!("CMZ", Answer::Text("CMZ".to_string()).to_string());
+    }
+
+    #[test]
+    fn converts_to_part() {
+        assert_eq!(Ok(Part::One), Part::try_from(1));
+        assert_eq!(Ok(Part::Two), Part::try_from(2));
+        assert_eq!(Err("invalid part '3'".to_string()), Part::try_from(3));
+    }
+
+    #[test]
+    fn turns_panic_into_error() {
+        assert_eq!(
+            Err(Error::Panic("part 1 is broken".to_string())),
+            solve_guarded(&Panicking, Part::One, &mut "".as_bytes())
+        );
+        assert_eq!(
+            Ok(Answer::Number(2)),
+            solve_guarded(&Panicking, Part::Two, &mut "".as_bytes())
+        );
+    }
+}
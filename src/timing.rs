@@ -0,0 +1,132 @@
+use std::{
+    cell::RefCell,
+    time::{Duration, Instant},
+};
+
+use tracing::{debug_span, span::EnteredSpan};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Phase {
+    Parse,
+    Solve,
+}
+
+#[derive(PartialEq, Debug, Default, Clone, Copy)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+impl Phase {
+    /// Span of the phase, so that logged events tell which phase they happened in.
+    fn span(self) -> EnteredSpan {
+        match self {
+            Phase::Parse => debug_span!("parse").entered(),
+            Phase::Solve => debug_span!("solve").entered(),
+        }
+    }
+}
+
+struct Recorder {
+    phase: Phase,
+    phase_start: Instant,
+    timings: Timings,
+    span: Option<EnteredSpan>,
+}
+
+impl Recorder {
+    fn start() -> Self {
+        Recorder {
+            phase: Phase::Parse,
+            phase_start: Instant::now(),
+            timings: Timings::default(),
+            span: Some(Phase::Parse.span()),
+        }
+    }
+
+    fn switch(&mut self, phase: Phase) {
+        self.finish();
+        self.phase = phase;
+        self.span = Some(phase.span());
+    }
+
+    /// Adds the time since the start of the current phase and leaves its span.
+    fn finish(&mut self) {
+        let now = Instant::now();
+        let elapsed = now - self.phase_start;
+        match self.phase {
+            Phase::Parse => self.timings.parse += elapsed,
+            Phase::Solve => self.timings.solve += elapsed,
+        }
+
+        self.phase_start = now;
+        self.span = None;
+    }
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Marks the start of a phase. Solvers start in the parse phase and call this once the input is parsed.
+/// Does nothing unless called within `measure`.
+pub fn enter(phase: Phase) {
+    RECORDER.with(|x| {
+        if let Some(recorder) = x.borrow_mut().as_mut() {
+            recorder.switch(phase);
+        }
+    });
+}
+
+/// Runs `f`, splitting its wall time into phases marked with `enter`.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Timings) {
+    RECORDER.with(|x| *x.borrow_mut() = Some(Recorder::start()));
+
+    let result = f();
+
+    let mut recorder = RECORDER.with(|x| x.borrow_mut().take()).unwrap();
+    recorder.finish();
+    (result, recorder.timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn splits_time_into_phases() {
+        let (result, timings) = measure(|| {
+            thread::sleep(Duration::from_millis(20));
+            enter(Phase::Solve);
+            thread::sleep(Duration::from_millis(10));
+            1
+        });
+
+        assert_eq!(1, result);
+        assert!(timings.parse >= Duration::from_millis(20));
+        assert!(timings.solve >= Duration::from_millis(10));
+        assert!(timings.solve < timings.parse);
+    }
+
+    #[test]
+    fn counts_everything_as_parse_if_solve_is_never_entered() {
+        let (_, timings) = measure(|| thread::sleep(Duration::from_millis(5)));
+
+        assert!(timings.parse >= Duration::from_millis(5));
+        assert_eq!(Duration::ZERO, timings.solve);
+    }
+
+    #[test]
+    fn ignores_phases_outside_of_measure() {
+        enter(Phase::Solve);
+        assert!(RECORDER.with(|x| x.borrow().is_none()));
+    }
+}
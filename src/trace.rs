@@ -0,0 +1,60 @@
+use std::cell::RefCell;
+
+use tracing::{trace, Level};
+
+thread_local! {
+    static STEPS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Records a step of the solution, which is also logged as a trace event. The message is only built within
+/// `record` or if trace events are logged, so solvers can narrate freely without slowing down regular runs.
+pub fn step<F: FnOnce() -> String>(message: F) {
+    let logged = tracing::enabled!(Level::TRACE);
+    STEPS.with(|x| {
+        let mut steps = x.borrow_mut();
+        if steps.is_none() && !logged {
+            return;
+        }
+
+        let message = message();
+        if logged {
+            trace!("{}", message);
+        }
+        if let Some(steps) = steps.as_mut() {
+            steps.push(message);
+        }
+    });
+}
+
+/// Runs `f`, collecting the steps it records with `step`.
+pub fn record<T, F: FnOnce() -> T>(f: F) -> (T, Vec<String>) {
+    STEPS.with(|x| *x.borrow_mut() = Some(Vec::new()));
+
+    let result = f();
+
+    let steps = STEPS.with(|x| x.borrow_mut().take()).unwrap_or_default();
+    (result, steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_steps_in_order() {
+        let (result, steps) = record(|| {
+            step(|| "first".to_string());
+            step(|| format!("second {}", 2));
+            3
+        });
+
+        assert_eq!(3, result);
+        assert_eq!(vec!["first", "second 2"], steps);
+    }
+
+    #[test]
+    fn ignores_steps_outside_of_record() {
+        step(|| panic!("message is built outside of record"));
+        assert!(STEPS.with(|x| x.borrow().is_none()));
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+
+use tracing::{info, info_span};
+
+use crate::answers::Answers;
+use crate::error::Error;
+use crate::input;
+use crate::solver::{self, Answer, Part, Solver};
+
+#[derive(PartialEq, Debug)]
+pub enum Outcome {
+    Pass(Answer),
+    Mismatch { expected: Answer, actual: Answer },
+    Fail(Error),
+    Unverified(Answer),
+}
+
+impl Outcome {
+    pub fn new(result: Result<Answer, Error>, expected: Option<&Answer>) -> Self {
+        match (result, expected) {
+            (Err(error), _) => Outcome::Fail(error),
+            (Ok(actual), None) => Outcome::Unverified(actual),
+            (Ok(actual), Some(expected)) if actual == *expected => Outcome::Pass(actual),
+            (Ok(actual), Some(expected)) => Outcome::Mismatch {
+                expected: expected.clone(),
+                actual,
+            },
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, Outcome::Pass(_) | Outcome::Unverified(_))
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Pass(_) => "pass",
+            Outcome::Mismatch { .. } => "mismatch",
+            Outcome::Fail(_) => "fail",
+            Outcome::Unverified(_) => "unverified",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass(answer) | Outcome::Unverified(answer) => {
+                write!(f, "{} ({})", self.label(), answer)
+            }
+            Outcome::Mismatch { expected, actual } => write!(
+                f,
+                "{} (expected {}, got {})",
+                self.label(),
+                expected,
+                actual
+            ),
+            Outcome::Fail(error) => write!(f, "{} ({})", self.label(), error),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Verification {
+    pub day: u32,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+pub fn verify_day(
+    day: u32,
+    solver: &dyn Solver,
+    path: &str,
+    answers: &Answers,
+) -> Vec<Verification> {
+    let _span = info_span!("day", day).entered();
+    Part::ALL
+        .into_iter()
+        .map(|part| {
+            let _span = info_span!("part", part = part.number()).entered();
+            let result = input::read_input(path)
+                .and_then(|input| solver::solve_guarded(solver, part, &mut input.as_bytes()));
+
+            let outcome = Outcome::new(result, answers.get(day, part));
+            info!(%outcome, "verified");
+
+            Verification { day, part, outcome }
+        })
+        .collect()
+}
+
+#[derive(PartialEq, Debug, Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub mismatched: usize,
+    pub failed: usize,
+    pub unverified: usize,
+}
+
+impl Summary {
+    pub fn new(verifications: &[Verification]) -> Self {
+        Summary::from_outcomes(verifications.iter().map(|x| &x.outcome))
+    }
+
+    pub fn from_outcomes<'a, I: IntoIterator<Item = &'a Outcome>>(outcomes: I) -> Self {
+        let mut result = Summary::default();
+        for outcome in outcomes {
+            match outcome {
+                Outcome::Pass(_) => result.passed += 1,
+                Outcome::Mismatch { .. } => result.mismatched += 1,
+                Outcome::Fail(_) => result.failed += 1,
+                Outcome::Unverified(_) => result.unverified += 1,
+            }
+        }
+
+        result
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} mismatched, {} failed, {} unverified",
+            self.passed, self.mismatched, self.failed, self.unverified
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::registry::Registry;
+
+    #[test]
+    fn compares_answer_with_expected_one() {
+        let expected = Answer::Number(1);
+
+        assert_eq!(
+            Outcome::Pass(Answer::Number(1)),
+            Outcome::new(Ok(Answer::Number(1)), Some(&expected))
+        );
+        assert_eq!(
+            Outcome::Mismatch {
+                expected: Answer::Number(1),
+                actual: Answer::Number(2)
+            },
+            Outcome::new(Ok(Answer::Number(2)), Some(&expected))
+        );
+        assert_eq!(
+            Outcome::Unverified(Answer::Number(2)),
+            Outcome::new(Ok(Answer::Number(2)), None)
+        );
+        assert_eq!(
+            Outcome::Fail(Error::MissingDir("a".to_string())),
+            Outcome::new(Err(Error::MissingDir("a".to_string())), Some(&expected))
+        );
+    }
+
+    #[test]
+    fn fails_both_parts_if_input_is_missing() {
+        let registry = Registry::new();
+        let verifications = verify_day(
+            1,
+            registry.get(1).unwrap(),
+            "inputs/missing.txt",
+            &Answers::default(),
+        );
+
+        assert_eq!(
+            vec![
+                Verification {
+                    day: 1,
+                    part: Part::One,
+                    outcome: Outcome::Fail(Error::Io(io::ErrorKind::NotFound.into()))
+                },
+                Verification {
+                    day: 1,
+                    part: Part::Two,
+                    outcome: Outcome::Fail(Error::Io(io::ErrorKind::NotFound.into()))
+                },
+            ],
+            verifications
+        );
+    }
+
+    #[test]
+    fn summarizes_outcomes() {
+        let verifications = vec![
+            Verification {
+                day: 1,
+                part: Part::One,
+                outcome: Outcome::Pass(Answer::Number(1)),
+            },
+            Verification {
+                day: 1,
+                part: Part::Two,
+                outcome: Outcome::Unverified(Answer::Number(2)),
+            },
+            Verification {
+                day: 2,
+                part: Part::One,
+                outcome: Outcome::Fail(Error::MissingDir("a".to_string())),
+            },
+        ];
+
+        let summary = Summary::new(&verifications);
+        assert_eq!(
+            "1 passed, 0 mismatched, 1 failed, 1 unverified",
+            summary.to_string()
+        );
+    }
+}
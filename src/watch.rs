@@ -0,0 +1,158 @@
+use std::{fmt, fs, time::SystemTime};
+
+use crate::error::Error;
+use crate::solver::Answer;
+
+/// Polls files for changes. A file counts as changed when its modification time or size differs from the last
+/// poll, including when it's created or removed.
+pub struct Watcher {
+    paths: Vec<String>,
+    stamps: Vec<Option<(SystemTime, u64)>>,
+}
+
+impl Watcher {
+    pub fn new(paths: &[String]) -> Self {
+        Watcher {
+            paths: paths.to_vec(),
+            stamps: paths.iter().map(|x| stamp(x)).collect(),
+        }
+    }
+
+    pub fn poll(&mut self) -> Vec<String> {
+        let mut changed = Vec::new();
+        for (path, previous) in self.paths.iter().zip(self.stamps.iter_mut()) {
+            let current = stamp(path);
+            if current != *previous {
+                *previous = current;
+                changed.push(path.clone());
+            }
+        }
+
+        changed
+    }
+}
+
+fn stamp(path: &str) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Diff {
+    First(Result<Answer, Error>),
+    Unchanged(Result<Answer, Error>),
+    Changed {
+        previous: Result<Answer, Error>,
+        current: Result<Answer, Error>,
+    },
+}
+
+impl Diff {
+    pub fn new(previous: Option<Result<Answer, Error>>, current: Result<Answer, Error>) -> Self {
+        match previous {
+            None => Diff::First(current),
+            Some(previous) if previous == current => Diff::Unchanged(current),
+            Some(previous) => Diff::Changed { previous, current },
+        }
+    }
+
+    pub fn current(&self) -> &Result<Answer, Error> {
+        match self {
+            Diff::First(current) | Diff::Unchanged(current) | Diff::Changed { current, .. } => {
+                current
+            }
+        }
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diff::First(current) => write!(f, "{}", ResultDisplay(current)),
+            Diff::Unchanged(current) => write!(f, "unchanged ({})", ResultDisplay(current)),
+            Diff::Changed { previous, current } => write!(
+                f,
+                "changed from {} to {}",
+                ResultDisplay(previous),
+                ResultDisplay(current)
+            ),
+        }
+    }
+}
+
+struct ResultDisplay<'a>(&'a Result<Answer, Error>);
+
+impl fmt::Display for ResultDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Ok(answer) => write!(f, "{}", answer),
+            Err(error) => write!(f, "error '{}'", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    #[test]
+    fn detects_created_modified_and_removed_files() {
+        let path = env::temp_dir()
+            .join(format!("aoc2022-watch-{}.txt", process::id()))
+            .to_string_lossy()
+            .to_string();
+        let _ = fs::remove_file(&path);
+
+        let mut watcher = Watcher::new(std::slice::from_ref(&path));
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&path, "1").unwrap();
+        assert_eq!(vec![path.clone()], watcher.poll());
+        assert!(watcher.poll().is_empty());
+
+        // Size changes even if the modification time has a coarse resolution
+        fs::write(&path, "12").unwrap();
+        assert_eq!(vec![path.clone()], watcher.poll());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(vec![path.clone()], watcher.poll());
+    }
+
+    #[test]
+    fn diffs_against_previous_answer() {
+        assert_eq!(
+            Diff::First(Ok(Answer::Number(1))),
+            Diff::new(None, Ok(Answer::Number(1)))
+        );
+        assert_eq!(
+            Diff::Unchanged(Ok(Answer::Number(1))),
+            Diff::new(Some(Ok(Answer::Number(1))), Ok(Answer::Number(1)))
+        );
+        assert_eq!(
+            Diff::Changed {
+                previous: Ok(Answer::Number(1)),
+                current: Ok(Answer::Number(2))
+            },
+            Diff::new(Some(Ok(Answer::Number(1))), Ok(Answer::Number(2)))
+        );
+    }
+
+    #[test]
+    fn displays_diff() {
+        assert_eq!("1", Diff::First(Ok(Answer::Number(1))).to_string());
+        assert_eq!(
+            "unchanged (CMZ)",
+            Diff::Unchanged(Ok(Answer::Text("CMZ".to_string()))).to_string()
+        );
+        assert_eq!(
+            "changed from 1 to error 'no solution: none'",
+            Diff::Changed {
+                previous: Ok(Answer::Number(1)),
+                current: Err(Error::NoSolution("none".to_string()))
+            }
+            .to_string()
+        );
+    }
+}
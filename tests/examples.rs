@@ -0,0 +1,28 @@
+use aoc2022::answers::Answers;
+use aoc2022::registry::{self, Registry};
+use aoc2022::verify::{self, Outcome};
+
+#[test]
+fn solves_all_examples() {
+    let registry = Registry::new();
+    let answers = Answers::load(registry::EXAMPLE_ANSWERS_PATH).unwrap();
+
+    let mut failures = Vec::new();
+    for (day, solver) in registry.iter() {
+        let path = registry::example_input_path(day);
+        for verification in verify::verify_day(day, solver, &path, &answers) {
+            // Parts without an answer in the manifest yet, e.g. of a day just created with new-day, are skipped
+            if answers.get(day, verification.part).is_none() {
+                continue;
+            }
+            if !matches!(verification.outcome, Outcome::Pass(_)) {
+                failures.push(format!(
+                    "Day {}, part {}: {}",
+                    verification.day, verification.part, verification.outcome
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,53 @@
+use aoc2022::day5::lib::{Order, Stack};
+use aoc2022::day6::lib::Slideable;
+use aoc2022::day7::file_system::FileSystem;
+use aoc2022::day8::tree_grid::TreeGrid;
+use aoc2022::registry::Registry;
+use aoc2022::solver::{Answer, Part};
+
+#[test]
+fn uses_stack_outside_of_day5() {
+    let mut stack: Stack<_> = "abc".chars().collect();
+
+    let popped: String = stack.pop_many_iter(2, Order::Lifo).unwrap().collect();
+    assert_eq!("cb", popped);
+    assert_eq!(Some(&'a'), stack.top());
+}
+
+#[test]
+fn uses_sliding_window_outside_of_day6() {
+    let windows: Vec<_> = [1, 2, 3].as_slice().sliding_window_iter(2).collect();
+
+    assert_eq!(vec![[1, 2].as_slice(), [2, 3].as_slice()], windows);
+}
+
+#[test]
+fn uses_file_system_outside_of_day7() {
+    let mut fs = FileSystem::new();
+    fs.add_file("a".to_string(), 12).unwrap();
+    fs.add_dir("b".to_string()).unwrap();
+    fs.cd("b").unwrap();
+    fs.add_file("c".to_string(), 34).unwrap();
+
+    let root_dir = fs.dirs_iter().next().unwrap();
+    assert_eq!(46, fs.dir_size(root_dir));
+}
+
+#[test]
+fn uses_tree_grid_outside_of_day8() {
+    let lines = vec!["111".to_string(), "121".to_string(), "111".to_string()];
+    let grid = TreeGrid::parse(lines.into_iter()).unwrap();
+
+    assert_eq!(9, grid.tree_iter().filter(|x| grid.tree_visible(x)).count());
+}
+
+#[test]
+fn solves_through_registry() {
+    let registry = Registry::new();
+    let solver = registry.get(4).unwrap();
+
+    assert_eq!(
+        Ok(Answer::Number(1)),
+        solver.solve(Part::One, &mut "2-8,3-7\n2-4,6-8\n".as_bytes())
+    );
+}
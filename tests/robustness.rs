@@ -0,0 +1,67 @@
+use std::{env, fs};
+
+use aoc2022::error::Error;
+use aoc2022::fuzz;
+use aoc2022::generate;
+use aoc2022::registry::{self, Registry};
+use aoc2022::solver::{self, Part};
+use fastrand::Rng;
+
+/// Inputs tried per day, `AOC_FUZZ_ITERATIONS` raises it for longer runs.
+const DEFAULT_ITERATIONS: usize = 300;
+
+fn iterations() -> usize {
+    env::var("AOC_FUZZ_ITERATIONS")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_ITERATIONS)
+}
+
+/// Runs both parts of every day on inputs from `input` and collects the ones that made a solver panic.
+fn find_panics(mut input: impl FnMut(&mut Rng, &[String]) -> String) -> Vec<String> {
+    let registry = Registry::new();
+    let mut failures = Vec::new();
+
+    for (day, solver) in registry.iter() {
+        let mut seeds =
+            vec![fs::read_to_string(registry::example_input_path(day)).unwrap_or_default()];
+        seeds.extend((1..=3).filter_map(|x| generate::generate(day, x * 5, x as u64)));
+
+        let mut rng = Rng::with_seed(day as u64);
+        for _ in 0..iterations() {
+            let input = input(&mut rng, &seeds);
+            for part in Part::ALL {
+                if let Err(Error::Panic(message)) =
+                    solver::solve_guarded(solver, part, &mut input.as_bytes())
+                {
+                    failures.push(format!(
+                        "Day {}, part {}: {} on input {:?}",
+                        day, part, message, input
+                    ));
+                }
+            }
+        }
+    }
+
+    failures
+}
+
+#[test]
+fn never_panics_on_mutated_inputs() {
+    let failures = find_panics(|rng, seeds| {
+        let seed = &seeds[rng.usize(..seeds.len())];
+        fuzz::mutate(rng, seed)
+    });
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn never_panics_on_noise() {
+    let failures = find_panics(|rng, _| {
+        let length = rng.usize(..64);
+        fuzz::noise(rng, length)
+    });
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}